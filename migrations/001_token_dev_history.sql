@@ -0,0 +1,8 @@
+-- Dev wallet history: who deployed a token, how far it ran and whether it rugged.
+ALTER TABLE tokens
+    ADD COLUMN IF NOT EXISTS dev TEXT,
+    ADD COLUMN IF NOT EXISTS peak_fdv DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS last_fdv DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS rugged BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_tokens_dev ON tokens (dev);
@@ -0,0 +1,7 @@
+-- FDVs are stored as exact decimals so call multipliers do not drift.
+ALTER TABLE token_scans
+    ALTER COLUMN fdv TYPE NUMERIC USING fdv::numeric;
+
+ALTER TABLE tokens
+    ALTER COLUMN peak_fdv TYPE NUMERIC USING peak_fdv::numeric,
+    ALTER COLUMN last_fdv TYPE NUMERIC USING last_fdv::numeric;
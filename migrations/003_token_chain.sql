@@ -0,0 +1,6 @@
+-- Chain of each token, EVM addresses are scanned alongside Solana mints.
+ALTER TABLE tokens
+    ADD COLUMN IF NOT EXISTS chain TEXT NOT NULL DEFAULT 'solana';
+
+ALTER TABLE token_scans
+    ADD COLUMN IF NOT EXISTS chain TEXT NOT NULL DEFAULT 'solana';
@@ -0,0 +1,5 @@
+-- A call is kept even without a price, its FDV is then unknown rather than 0.
+ALTER TABLE token_scans
+    ALTER COLUMN fdv DROP NOT NULL;
+
+UPDATE token_scans SET fdv = NULL WHERE fdv = 0;
@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Entries kept before expired ones are swept on insert.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+struct CacheEntry {
+    body: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+/// In-process cache of raw upstream response bodies keyed by request URL.
+/// Every entry carries its own TTL, so one cache serves all endpoints of a client.
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.body.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, key: String, body: String, ttl: Duration) {
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+
+        if entries.len() >= SWEEP_THRESHOLD {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+
+        entries.insert(
+            key,
+            CacheEntry {
+                body,
+                expires_at: now + ttl,
+            },
+        );
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::advance;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn insert(cache: &ResponseCache, key: &str) {
+        cache.insert(String::from(key), format!("body of {}", key), TTL);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn serves_entries_until_they_expire() {
+        let cache = ResponseCache::new();
+        insert(&cache, "/tokens/mint");
+
+        advance(TTL - Duration::from_millis(1)).await;
+        assert_eq!(cache.get("/tokens/mint").unwrap(), "body of /tokens/mint");
+
+        advance(Duration::from_millis(1)).await;
+        assert_eq!(cache.get("/tokens/mint"), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn counts_hits_and_misses() {
+        let cache = ResponseCache::new();
+
+        assert_eq!(cache.get("/tokens/mint"), None);
+        insert(&cache, "/tokens/mint");
+        cache.get("/tokens/mint");
+        cache.get("/tokens/mint");
+        advance(TTL).await;
+        cache.get("/tokens/mint");
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn skips_zero_ttl() {
+        let cache = ResponseCache::new();
+        cache.insert(String::from("/tokens/mint"), String::new(), Duration::ZERO);
+
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reinsert_extends_the_ttl() {
+        let cache = ResponseCache::new();
+        insert(&cache, "/tokens/mint");
+
+        advance(TTL / 2).await;
+        insert(&cache, "/tokens/mint");
+        advance(TTL / 2).await;
+
+        assert!(cache.get("/tokens/mint").is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sweeps_expired_entries_at_the_threshold() {
+        let cache = ResponseCache::new();
+        for i in 0..SWEEP_THRESHOLD - 1 {
+            insert(&cache, &i.to_string());
+        }
+        advance(TTL).await;
+
+        // Below the threshold expired entries stay until they are read.
+        insert(&cache, "fresh");
+        assert_eq!(cache.stats().entries, SWEEP_THRESHOLD);
+
+        insert(&cache, "fresher");
+        assert_eq!(cache.stats().entries, 2);
+    }
+}
@@ -0,0 +1,330 @@
+use super::errors::{ApiError, ApiErrorData};
+use crate::errors::AppError;
+use reqwest::StatusCode;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerSettings {
+    /// Consecutive upstream failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe request is let through.
+    pub open_duration: Duration,
+}
+
+enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probe_started_at: Instant },
+}
+
+/// Stops sending requests to an upstream after repeated failures, so scans
+/// fail fast and fall back to other sources instead of waiting on timeouts.
+pub struct CircuitBreaker {
+    source: &'static str,
+    settings: CircuitBreakerSettings,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(source: &'static str, settings: CircuitBreakerSettings) -> Self {
+        Self {
+            source,
+            settings,
+            state: Mutex::new(CircuitState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Runs `fut` unless the circuit is open, and records its outcome. A `fut`
+    /// dropped before it finishes, e.g. by a scan timeout, counts as a failure.
+    pub async fn run<T, F>(&self, fut: F) -> Result<T, AppError>
+    where
+        F: Future<Output = Result<T, AppError>>,
+    {
+        if !self.allow() {
+            return Err(ApiError::ServiceUnavailable {
+                error_data: ApiErrorData {
+                    source: String::from(self.source),
+                    status_code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+                    message: String::from("Circuit open, request skipped"),
+                },
+            }
+            .into());
+        }
+
+        let mut pending = PendingRequest {
+            circuit_breaker: self,
+            finished: false,
+        };
+        let result = fut.await;
+        pending.finished = true;
+
+        match &result {
+            Err(e) if Self::is_upstream_failure(e) => self.record_failure(),
+            _ => self.record_success(),
+        }
+
+        result
+    }
+
+    fn allow(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { until } if now >= until => {
+                *state = CircuitState::HalfOpen {
+                    probe_started_at: now,
+                };
+                true
+            }
+            CircuitState::Open { .. } => false,
+            // A probe that never reported back is replaced.
+            CircuitState::HalfOpen { probe_started_at }
+                if now.duration_since(probe_started_at) >= self.settings.open_duration =>
+            {
+                *state = CircuitState::HalfOpen {
+                    probe_started_at: now,
+                };
+                true
+            }
+            CircuitState::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        if let CircuitState::HalfOpen { .. } = *state {
+            info!("{} | circuit closed", self.source);
+        }
+
+        *state = CircuitState::Closed { failures: 0 };
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        let failures = match *state {
+            CircuitState::Closed { failures } => failures + 1,
+            _ => self.settings.failure_threshold,
+        };
+
+        if failures >= self.settings.failure_threshold {
+            warn!(
+                "{} | circuit opened for {:?} after {} failures",
+                self.source, self.settings.open_duration, failures
+            );
+            *state = CircuitState::Open {
+                until: Instant::now() + self.settings.open_duration,
+            };
+        } else {
+            *state = CircuitState::Closed { failures };
+        }
+    }
+
+    /// Outages, throttling and unreadable responses count against the
+    /// upstream; "not found" style answers do not.
+    fn is_upstream_failure(error: &AppError) -> bool {
+        match error.root() {
+            AppError::Api(api_error) => match api_error {
+                ApiError::TooManyRequests { .. }
+                | ApiError::InternalServerError { .. }
+                | ApiError::ServiceUnavailable { .. }
+                | ApiError::BadGateway { .. }
+                | ApiError::GatewayTimeout { .. } => true,
+                ApiError::Unknown { error_data } => error_data.status_code >= 500,
+                _ => false,
+            },
+            AppError::Request(_) | AppError::Json(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Records a failure if the request is dropped mid-flight, since an upstream
+/// too slow to answer within the scan timeout is as unusable as a failing one.
+struct PendingRequest<'a> {
+    circuit_breaker: &'a CircuitBreaker,
+    finished: bool,
+}
+
+impl Drop for PendingRequest<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.circuit_breaker.record_failure();
+        }
+    }
+}
+
+/// The circuit breaker of every upstream.
+pub struct CircuitBreakers {
+    pub moralis: Arc<CircuitBreaker>,
+    pub jupiter: Arc<CircuitBreaker>,
+    pub dexscreener: Arc<CircuitBreaker>,
+    pub solana_rpc: Arc<CircuitBreaker>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::time::{advance, timeout};
+
+    const OPEN_DURATION: Duration = Duration::from_secs(30);
+
+    fn circuit_breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            "Test",
+            CircuitBreakerSettings {
+                failure_threshold: 2,
+                open_duration: OPEN_DURATION,
+            },
+        )
+    }
+
+    fn error_data(status_code: StatusCode) -> ApiErrorData {
+        ApiErrorData {
+            source: String::from("Test"),
+            status_code: status_code.as_u16(),
+            message: String::new(),
+        }
+    }
+
+    async fn fail(circuit_breaker: &CircuitBreaker) -> Result<(), AppError> {
+        circuit_breaker
+            .run(async {
+                Err(ApiError::BadGateway {
+                    error_data: error_data(StatusCode::BAD_GATEWAY),
+                }
+                .into())
+            })
+            .await
+    }
+
+    async fn succeed(circuit_breaker: &CircuitBreaker) -> Result<(), AppError> {
+        circuit_breaker.run(async { Ok(()) }).await
+    }
+
+    fn state(circuit_breaker: &CircuitBreaker) -> &'static str {
+        match *circuit_breaker.state.lock().unwrap() {
+            CircuitState::Closed { .. } => "closed",
+            CircuitState::Open { .. } => "open",
+            CircuitState::HalfOpen { .. } => "half-open",
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_probes_and_closes_again() {
+        let circuit_breaker = circuit_breaker();
+
+        fail(&circuit_breaker).await.unwrap_err();
+        assert_eq!(state(&circuit_breaker), "closed");
+        fail(&circuit_breaker).await.unwrap_err();
+        assert_eq!(state(&circuit_breaker), "open");
+
+        // Open: requests are skipped without reaching the upstream.
+        let runs = AtomicU32::new(0);
+        let skipped = circuit_breaker
+            .run(async {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+        assert!(matches!(
+            skipped.unwrap_err(),
+            AppError::Api(ApiError::ServiceUnavailable { .. })
+        ));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        advance(OPEN_DURATION).await;
+
+        // Half-open: one probe goes through, its success closes the circuit.
+        succeed(&circuit_breaker).await.unwrap();
+        assert_eq!(state(&circuit_breaker), "closed");
+        succeed(&circuit_breaker).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_probe_reopens() {
+        let circuit_breaker = circuit_breaker();
+        fail(&circuit_breaker).await.unwrap_err();
+        fail(&circuit_breaker).await.unwrap_err();
+
+        advance(OPEN_DURATION).await;
+        fail(&circuit_breaker).await.unwrap_err();
+        assert_eq!(state(&circuit_breaker), "open");
+
+        advance(OPEN_DURATION / 2).await;
+        assert!(succeed(&circuit_breaker).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lets_one_probe_through_at_a_time() {
+        let circuit_breaker = circuit_breaker();
+        fail(&circuit_breaker).await.unwrap_err();
+        fail(&circuit_breaker).await.unwrap_err();
+        advance(OPEN_DURATION).await;
+
+        assert!(circuit_breaker.allow());
+        assert_eq!(state(&circuit_breaker), "half-open");
+        assert!(succeed(&circuit_breaker).await.is_err());
+
+        // A probe that never reports back is replaced after a while.
+        advance(OPEN_DURATION).await;
+        succeed(&circuit_breaker).await.unwrap();
+        assert_eq!(state(&circuit_breaker), "closed");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn success_resets_the_failure_count() {
+        let circuit_breaker = circuit_breaker();
+
+        fail(&circuit_breaker).await.unwrap_err();
+        succeed(&circuit_breaker).await.unwrap();
+        fail(&circuit_breaker).await.unwrap_err();
+
+        assert_eq!(state(&circuit_breaker), "closed");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn not_found_does_not_count() {
+        let circuit_breaker = circuit_breaker();
+
+        for _ in 0..3 {
+            let result: Result<(), AppError> = circuit_breaker
+                .run(async {
+                    Err(ApiError::NotFound {
+                        error_data: error_data(StatusCode::NOT_FOUND),
+                    }
+                    .into())
+                })
+                .await;
+            result.unwrap_err();
+        }
+
+        assert_eq!(state(&circuit_breaker), "closed");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn abandoned_requests_count_as_failures() {
+        let circuit_breaker = circuit_breaker();
+
+        for _ in 0..2 {
+            let abandoned = timeout(
+                Duration::from_secs(5),
+                circuit_breaker.run(std::future::pending::<Result<(), AppError>>()),
+            )
+            .await;
+            assert!(abandoned.is_err());
+        }
+
+        assert_eq!(state(&circuit_breaker), "open");
+    }
+}
@@ -0,0 +1,2 @@
+pub const DEXSCREENER_BASE_URL: &str = "https://api.dexscreener.com";
+pub const SOLANA_CHAIN_ID: &str = "solana";
@@ -0,0 +1,241 @@
+use super::constants::SOLANA_CHAIN_ID;
+use super::models::{DexPair, DexPairsResponse};
+use crate::api::circuit_breaker::CircuitBreaker;
+use crate::api::errors::{ApiError, ApiErrorData, non_json_error_body};
+use crate::api::fixtures::FixtureStore;
+use crate::api::models::{
+    TokenHolderInfo, TokenLinks, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo,
+    TokenStats1H, TokenStats24H,
+};
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::retry::RetryPolicy;
+use crate::api::traits::TokenDataProvider;
+use crate::errors::AppError;
+use crate::errors::{ProviderError, TokenPairError};
+use crate::utils::decimal;
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::{Client, Method, Response, StatusCode};
+use serde_json::from_str;
+use std::sync::Arc;
+
+pub struct DexScreenerApiClient {
+    client: Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    fixtures: Arc<FixtureStore>,
+}
+
+impl DexScreenerApiClient {
+    pub fn new(
+        base_url: &str,
+        retry_policy: RetryPolicy,
+        rate_limiter: Arc<RateLimiter>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        fixtures: Arc<FixtureStore>,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            client: Client::builder()
+                .timeout(retry_policy.attempt_timeout)
+                .build()?,
+            base_url: String::from(base_url.trim_end_matches('/')),
+            retry_policy,
+            rate_limiter,
+            circuit_breaker,
+            fixtures,
+        })
+    }
+
+    /// Returns every pair DexScreener knows for the token, as listed by the
+    /// `token-pairs` endpoint.
+    pub async fn get_token_pairs(&self, token_address: &str) -> Result<Vec<DexPair>, AppError> {
+        let url = format!(
+            "{}/token-pairs/v1/{}/{}",
+            self.base_url, SOLANA_CHAIN_ID, token_address
+        );
+
+        let body = self.get(&url).await?;
+
+        let token_pairs: Vec<DexPair> = from_str(&body)?;
+
+        Ok(token_pairs)
+    }
+
+    pub async fn get_pair(&self, pair_address: &str) -> Result<DexPair, AppError> {
+        let url = format!(
+            "{}/latest/dex/pairs/{}/{}",
+            self.base_url, SOLANA_CHAIN_ID, pair_address
+        );
+
+        let body = self.get(&url).await?;
+
+        let pairs_response: DexPairsResponse = from_str(&body)?;
+
+        pairs_response
+            .pairs
+            .and_then(|pairs| pairs.into_iter().next())
+            .ok_or_else(|| Self::not_found("Pair not found"))
+    }
+
+    /// Picks the most liquid pair where the token is the base token.
+    pub async fn get_primary_token_pair(&self, token_address: &str) -> Result<DexPair, AppError> {
+        self.get_token_pairs(token_address)
+            .await?
+            .into_iter()
+            .filter(|pair| pair.base_token.address == token_address)
+            .max_by(|a, b| Self::liquidity_usd(a).total_cmp(&Self::liquidity_usd(b)))
+            .ok_or_else(|| {
+                TokenPairError::ActivePairNotFound {
+                    token_address: String::from(token_address),
+                }
+                .into()
+            })
+    }
+
+    fn liquidity_usd(pair: &DexPair) -> f64 {
+        pair.liquidity
+            .as_ref()
+            .and_then(|liquidity| liquidity.usd)
+            .unwrap_or_default()
+    }
+
+    fn not_found(message: &str) -> AppError {
+        ApiError::NotFound {
+            error_data: ApiErrorData {
+                source: String::from("DexScreener Api"),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+                message: String::from(message),
+            },
+        }
+        .into()
+    }
+
+    async fn get(&self, url: &str) -> Result<String, AppError> {
+        let endpoint = url.strip_prefix(&self.base_url).unwrap_or(url);
+
+        self.circuit_breaker
+            .run(async {
+                let response = self
+                    .fixtures
+                    .through("dexscreener", endpoint, async {
+                        Ok(self
+                            .retry_policy
+                            .send("DexScreener Api", &self.rate_limiter, || {
+                                self.client.request(Method::GET, url)
+                            })
+                            .await?)
+                    })
+                    .await?;
+                let response = self.handle_response(response).await?;
+
+                Ok(response.text().await?)
+            })
+            .await
+    }
+
+    async fn handle_response(&self, response: Response) -> Result<Response, AppError> {
+        let status_code = response.status().as_u16();
+
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        // DexScreener does not document an error body, keep an excerpt of it.
+        let error_data = ApiErrorData {
+            source: String::from("DexScreener Api"),
+            status_code,
+            message: non_json_error_body(&response.text().await?),
+        };
+
+        Err(ApiError::from_status(error_data).into())
+    }
+}
+
+#[async_trait]
+impl TokenDataProvider for DexScreenerApiClient {
+    fn name(&self) -> &'static str {
+        "dexscreener"
+    }
+
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError> {
+        let token_pair = self.get_primary_token_pair(token_address).await?;
+        let info = token_pair.info.unwrap_or_default();
+
+        let mut links = TokenLinks {
+            discord: None,
+            telegram: None,
+            reddit: None,
+            twitter: None,
+            website: info.websites.into_iter().next().map(|website| website.url),
+        };
+
+        for social in info.socials {
+            match social.social_type.as_str() {
+                "twitter" => links.twitter = Some(social.url),
+                "telegram" => links.telegram = Some(social.url),
+                "discord" => links.discord = Some(social.url),
+                "reddit" => links.reddit = Some(social.url),
+                _ => {}
+            }
+        }
+
+        Ok(TokenMetadataInfo {
+            mint: token_pair.base_token.address,
+            name: token_pair.base_token.name,
+            symbol: token_pair.base_token.symbol,
+            logo: info.image_url.unwrap_or_default(),
+            fully_diluted_value: decimal::from_opt_f64(token_pair.fdv)?,
+            links,
+        })
+    }
+
+    async fn fetch_primary_pair(&self, token_address: &str) -> Result<TokenPairInfo, AppError> {
+        let token_pair = self.get_primary_token_pair(token_address).await?;
+
+        Ok(TokenPairInfo {
+            mint: String::from(token_address),
+            pair_address: token_pair.pair_address,
+            exchange_name: token_pair.dex_id,
+        })
+    }
+
+    async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        let pair = self.get_pair(&token_pair.pair_address).await?;
+        let txns_1h = pair.txns.h1.clone().unwrap_or_default();
+
+        Ok(TokenPairStatsInfo {
+            usd_price: decimal::parse(
+                pair.price_usd
+                    .as_deref()
+                    .ok_or_else(|| Self::not_found("USD price not available"))?,
+            )?,
+            liquidity_usd: decimal::from_f64(Self::liquidity_usd(&pair))?,
+            stats_1h: TokenStats1H {
+                buys: txns_1h.buys,
+                sells: txns_1h.sells,
+                volume: pair.volume.h1.unwrap_or_default(),
+                price_percent_change: pair.price_change.h1.unwrap_or_default(),
+            },
+            stats_24h: TokenStats24H {
+                price_percent_change: pair.price_change.h24.unwrap_or_default(),
+            },
+            pair_created_at: pair
+                .pair_created_at
+                .and_then(DateTime::from_timestamp_millis),
+            exchange_name: pair.dex_id,
+        })
+    }
+
+    async fn fetch_holders(&self, _token_address: &str) -> Result<TokenHolderInfo, AppError> {
+        Err(ProviderError::Unsupported {
+            provider: String::from(self.name()),
+            field: String::from("holders"),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod constants;
+pub mod dexscreener_api_client;
+pub mod models;
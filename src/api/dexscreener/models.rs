@@ -0,0 +1,80 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DexPair {
+    #[serde(rename = "dexId")]
+    pub dex_id: String,
+    #[serde(rename = "pairAddress")]
+    pub pair_address: String,
+    #[serde(rename = "baseToken")]
+    pub base_token: DexToken,
+    #[serde(rename = "priceUsd")]
+    pub price_usd: Option<String>,
+    #[serde(default)]
+    pub txns: DexTxns,
+    #[serde(default)]
+    pub volume: DexWindowValues,
+    #[serde(rename = "priceChange", default)]
+    pub price_change: DexWindowValues,
+    pub liquidity: Option<DexLiquidity>,
+    pub fdv: Option<f64>,
+    #[serde(rename = "pairCreatedAt")]
+    pub pair_created_at: Option<i64>,
+    pub info: Option<DexInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DexToken {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DexTxns {
+    pub h1: Option<DexTxnCount>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DexTxnCount {
+    pub buys: u32,
+    pub sells: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DexWindowValues {
+    pub h1: Option<f64>,
+    pub h24: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DexLiquidity {
+    pub usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DexInfo {
+    #[serde(rename = "imageUrl")]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub websites: Vec<DexWebsite>,
+    #[serde(default)]
+    pub socials: Vec<DexSocial>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DexWebsite {
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DexSocial {
+    #[serde(rename = "type")]
+    pub social_type: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DexPairsResponse {
+    pub pairs: Option<Vec<DexPair>>,
+}
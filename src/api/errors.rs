@@ -15,10 +15,6 @@ pub enum ApiError {
     InternalServerError { error_data: ApiErrorData },
     #[error("{} | Service Unavailable: status: {}, {}", error_data.source, error_data.status_code, error_data.message)]
     ServiceUnavailable { error_data: ApiErrorData },
-    #[error("{} | Access Denied: status: {}, {}", error_data.source, error_data.status_code, error_data.message)]
-    AccessDenied { error_data: ApiErrorData },
-    #[error("{} | Missing API Key: status: {}, {}", error_data.source, error_data.status_code, error_data.message)]
-    MissingAPIKey { error_data: ApiErrorData },
     #[error("{} | Invalid API Key: status: {}, {}", error_data.source, error_data.status_code,error_data.message)]
     InvalidAPIKey { error_data: ApiErrorData },
     #[error("{} | Not Found: status: {}, {}", error_data.source, error_data.status_code, error_data.message)]
@@ -31,6 +27,56 @@ pub enum ApiError {
     Unknown { error_data: ApiErrorData },
 }
 
+impl ApiError {
+    /// Maps an upstream HTTP status to its variant.
+    pub fn from_status(error_data: ApiErrorData) -> Self {
+        match error_data.status_code {
+            400 => Self::BadRequest { error_data },
+            401 => Self::Unauthorized { error_data },
+            403 => Self::Forbidden { error_data },
+            404 => Self::NotFound { error_data },
+            429 => Self::TooManyRequests { error_data },
+            500 => Self::InternalServerError { error_data },
+            502 => Self::BadGateway { error_data },
+            503 => Self::ServiceUnavailable { error_data },
+            504 => Self::GatewayTimeout { error_data },
+            _ => Self::Unknown { error_data },
+        }
+    }
+
+    pub fn error_data(&self) -> &ApiErrorData {
+        match self {
+            Self::BadRequest { error_data }
+            | Self::Unauthorized { error_data }
+            | Self::Forbidden { error_data }
+            | Self::TooManyRequests { error_data }
+            | Self::InternalServerError { error_data }
+            | Self::ServiceUnavailable { error_data }
+            | Self::InvalidAPIKey { error_data }
+            | Self::NotFound { error_data }
+            | Self::BadGateway { error_data }
+            | Self::GatewayTimeout { error_data }
+            | Self::Unknown { error_data } => error_data,
+        }
+    }
+}
+
+/// Error bodies are not always JSON, e.g. the HTML page of a proxy in front of
+/// the upstream. Keeps a short single-line excerpt of those for the logs.
+pub fn non_json_error_body(body: &str) -> String {
+    const MAX_EXCERPT_LENGTH: usize = 120;
+
+    let excerpt: String = body
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .take(MAX_EXCERPT_LENGTH)
+        .collect();
+
+    format!("Non-JSON error body: {}", excerpt)
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiErrorData {
     pub source: String,
@@ -0,0 +1,325 @@
+use super::{
+    models::{TokenHolderInfo, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo},
+    traits::TokenDataProvider,
+};
+use crate::errors::AppError;
+use crate::errors::{ProviderError, ScanError};
+use async_trait::async_trait;
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::time::timeout;
+use tracing::warn;
+
+pub struct ProviderChains {
+    pub metadata: Vec<Arc<dyn TokenDataProvider>>,
+    pub primary_pair: Vec<Arc<dyn TokenDataProvider>>,
+    pub pair_stats: Vec<Arc<dyn TokenDataProvider>>,
+    pub holders: Vec<Arc<dyn TokenDataProvider>>,
+}
+
+/// Tries every provider of a chain in order and returns the first successful
+/// result, or the last error if all of them failed. Every provider but the
+/// last gets `provider_timeout`, so a slow one does not starve its fallbacks.
+pub struct FallbackTokenDataProvider {
+    chains: ProviderChains,
+    provider_timeout: Duration,
+}
+
+impl FallbackTokenDataProvider {
+    pub fn new(chains: ProviderChains, provider_timeout: Duration) -> Self {
+        Self {
+            chains,
+            provider_timeout,
+        }
+    }
+
+    async fn attempt<T>(
+        &self,
+        provider: &dyn TokenDataProvider,
+        is_last: bool,
+        fut: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        if is_last {
+            return fut.await;
+        }
+
+        match timeout(self.provider_timeout, fut).await {
+            Ok(result) => result,
+            Err(_) => Err(ScanError::Timeout {
+                section: provider.name(),
+                after: self.provider_timeout,
+            }
+            .into()),
+        }
+    }
+
+    fn exhausted(field: &str, last_error: Option<AppError>) -> AppError {
+        match last_error {
+            Some(error) => error,
+            None => ProviderError::NoSources {
+                field: String::from(field),
+            }
+            .into(),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenDataProvider for FallbackTokenDataProvider {
+    fn name(&self) -> &'static str {
+        "fallback"
+    }
+
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError> {
+        let mut last_error = None;
+
+        let chain = &self.chains.metadata;
+        for (position, provider) in chain.iter().enumerate() {
+            let is_last = position + 1 == chain.len();
+            match self
+                .attempt(
+                    provider.as_ref(),
+                    is_last,
+                    provider.fetch_metadata(token_address),
+                )
+                .await
+            {
+                Ok(metadata) => return Ok(metadata),
+                Err(e) => {
+                    warn!("{} | metadata lookup failed: {}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Self::exhausted("metadata", last_error))
+    }
+
+    async fn fetch_primary_pair(&self, token_address: &str) -> Result<TokenPairInfo, AppError> {
+        let mut last_error = None;
+
+        let chain = &self.chains.primary_pair;
+        for (position, provider) in chain.iter().enumerate() {
+            let is_last = position + 1 == chain.len();
+            match self
+                .attempt(
+                    provider.as_ref(),
+                    is_last,
+                    provider.fetch_primary_pair(token_address),
+                )
+                .await
+            {
+                Ok(token_pair) => return Ok(token_pair),
+                Err(e) => {
+                    warn!("{} | primary pair lookup failed: {}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Self::exhausted("primary pair", last_error))
+    }
+
+    async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        let mut last_error = None;
+
+        let chain = &self.chains.pair_stats;
+        for (position, provider) in chain.iter().enumerate() {
+            let is_last = position + 1 == chain.len();
+            match self
+                .attempt(
+                    provider.as_ref(),
+                    is_last,
+                    provider.fetch_pair_stats(token_pair),
+                )
+                .await
+            {
+                Ok(pair_stats) => return Ok(pair_stats),
+                Err(e) => {
+                    warn!("{} | pair stats lookup failed: {}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Self::exhausted("pair stats", last_error))
+    }
+
+    async fn fetch_holders(&self, token_address: &str) -> Result<TokenHolderInfo, AppError> {
+        let mut last_error = None;
+
+        let chain = &self.chains.holders;
+        for (position, provider) in chain.iter().enumerate() {
+            let is_last = position + 1 == chain.len();
+            match self
+                .attempt(
+                    provider.as_ref(),
+                    is_last,
+                    provider.fetch_holders(token_address),
+                )
+                .await
+            {
+                Ok(holders) => return Ok(holders),
+                Err(e) => {
+                    warn!("{} | holders lookup failed: {}", provider.name(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Self::exhausted("holders", last_error))
+    }
+}
+
+/// Resolves a configured list of source names into an ordered provider chain.
+pub fn build_chain(
+    providers: &[Arc<dyn TokenDataProvider>],
+    order: &[String],
+) -> Result<Vec<Arc<dyn TokenDataProvider>>, AppError> {
+    order
+        .iter()
+        .map(|name| {
+            providers
+                .iter()
+                .find(|provider| provider.name() == name)
+                .cloned()
+                .ok_or_else(|| ProviderError::UnknownSource { name: name.clone() }.into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::{Instant, sleep};
+
+    const PROVIDER_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Answers holder lookups with `holders` after `delay`, or with an
+    /// "unsupported" error when `holders` is `None`.
+    struct FakeProvider {
+        name: &'static str,
+        delay: Duration,
+        holders: Option<u32>,
+    }
+
+    impl FakeProvider {
+        fn unsupported(&self, field: &str) -> AppError {
+            ProviderError::Unsupported {
+                provider: String::from(self.name),
+                field: String::from(field),
+            }
+            .into()
+        }
+    }
+
+    #[async_trait]
+    impl TokenDataProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn fetch_metadata(&self, _: &str) -> Result<TokenMetadataInfo, AppError> {
+            Err(self.unsupported("metadata"))
+        }
+
+        async fn fetch_primary_pair(&self, _: &str) -> Result<TokenPairInfo, AppError> {
+            Err(self.unsupported("primary pair"))
+        }
+
+        async fn fetch_pair_stats(
+            &self,
+            _: &TokenPairInfo,
+        ) -> Result<TokenPairStatsInfo, AppError> {
+            Err(self.unsupported("pair stats"))
+        }
+
+        async fn fetch_holders(&self, _: &str) -> Result<TokenHolderInfo, AppError> {
+            sleep(self.delay).await;
+
+            match self.holders {
+                Some(total_holders) => Ok(TokenHolderInfo { total_holders }),
+                None => Err(self.unsupported("holders")),
+            }
+        }
+    }
+
+    fn provider(
+        name: &'static str,
+        delay_secs: u64,
+        holders: Option<u32>,
+    ) -> Arc<dyn TokenDataProvider> {
+        Arc::new(FakeProvider {
+            name,
+            delay: Duration::from_secs(delay_secs),
+            holders,
+        })
+    }
+
+    fn fallback(holders: Vec<Arc<dyn TokenDataProvider>>) -> FallbackTokenDataProvider {
+        FallbackTokenDataProvider::new(
+            ProviderChains {
+                metadata: vec![],
+                primary_pair: vec![],
+                pair_stats: vec![],
+                holders,
+            },
+            PROVIDER_TIMEOUT,
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_provider_leaves_time_for_the_fallback() {
+        let fallback = fallback(vec![
+            provider("slow", 10, Some(1)),
+            provider("fast", 1, Some(2)),
+        ]);
+
+        let start = Instant::now();
+        let holders = fallback.fetch_holders("mint").await.unwrap();
+
+        assert_eq!(holders.total_holders, 2);
+        assert_eq!(start.elapsed(), PROVIDER_TIMEOUT + Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn last_provider_is_not_capped() {
+        let fallback = fallback(vec![
+            provider("failing", 0, None),
+            provider("slow", 10, Some(2)),
+        ]);
+
+        let start = Instant::now();
+        let holders = fallback.fetch_holders("mint").await.unwrap();
+
+        assert_eq!(holders.total_holders, 2);
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn returns_the_last_error_when_every_provider_fails() {
+        let fallback = fallback(vec![
+            provider("slow", 10, Some(1)),
+            provider("failing", 0, None),
+        ]);
+
+        let error = fallback.fetch_holders("mint").await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            AppError::Provider(ProviderError::Unsupported { provider, .. }) if provider == "failing"
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn empty_chain_has_no_sources() {
+        let error = fallback(vec![]).fetch_holders("mint").await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            AppError::Provider(ProviderError::NoSources { .. })
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+use super::errors::{ApiError, ApiErrorData};
+use crate::errors::AppError;
+use reqwest::{Response, StatusCode};
+use sha2::{Digest, Sha256};
+use std::{
+    future::Future,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tokio::fs;
+use tracing::info;
+
+/// Longest sanitized request key kept in a fixture file name.
+const MAX_KEY_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Talk to the upstreams only.
+    Off,
+    /// Talk to the upstreams and save every response they answer with.
+    Record,
+    /// Serve saved responses and never touch the network.
+    Replay,
+}
+
+impl FromStr for FixtureMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "off" => Ok(Self::Off),
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            other => Err(anyhow::Error::msg(format!(
+                "Unknown fixture mode \"{}\"",
+                other
+            ))),
+        }
+    }
+}
+
+/// Raw upstream responses on disk, one file per source and request key.
+pub struct FixtureStore {
+    mode: FixtureMode,
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    pub fn new(mode: FixtureMode, dir: &str) -> Self {
+        Self {
+            mode,
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    /// Runs `fetch` according to the mode: as-is, saving its response, or not
+    /// at all when replaying the response saved for `key`. Saved responses keep
+    /// their status, so errors replay through the client's response handling.
+    pub async fn through<F>(&self, source: &str, key: &str, fetch: F) -> Result<Response, AppError>
+    where
+        F: Future<Output = Result<Response, AppError>>,
+    {
+        match self.mode {
+            FixtureMode::Off => fetch.await,
+            FixtureMode::Record => {
+                let response = fetch.await?;
+                let status = response.status();
+                let body = response.text().await?;
+                let path = self.path(source, key);
+                let status_path = path.with_extension("status");
+
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).await?;
+                }
+                fs::write(&path, &body).await?;
+
+                // Bodies without a status file are successful responses.
+                if status.is_success() {
+                    match fs::remove_file(&status_path).await {
+                        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                        _ => {}
+                    }
+                } else {
+                    fs::write(&status_path, status.as_str()).await?;
+                }
+                info!("{} | recorded {} ({})", source, path.display(), status);
+
+                Ok(Self::response(status, body))
+            }
+            FixtureMode::Replay => {
+                let path = self.path(source, key);
+
+                let body = match fs::read_to_string(&path).await {
+                    Ok(body) => body,
+                    Err(_) => {
+                        return Err(ApiError::NotFound {
+                            error_data: ApiErrorData {
+                                source: String::from(source),
+                                status_code: StatusCode::NOT_FOUND.as_u16(),
+                                message: format!("No fixture at {}", path.display()),
+                            },
+                        }
+                        .into());
+                    }
+                };
+
+                let status = match fs::read_to_string(path.with_extension("status")).await {
+                    Ok(status) => StatusCode::from_str(status.trim())
+                        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?,
+                    Err(_) => StatusCode::OK,
+                };
+
+                Ok(Self::response(status, body))
+            }
+        }
+    }
+
+    fn response(status: StatusCode, body: String) -> Response {
+        let mut response = http::Response::new(body);
+        *response.status_mut() = status;
+
+        Response::from(response)
+    }
+
+    /// `<dir>/<source>/<sanitized key>-<key hash>.json`, so files stay readable
+    /// (endpoint and mint) while distinct keys never collide.
+    fn path(&self, source: &str, key: &str) -> PathBuf {
+        let sanitize = |value: &str| -> String {
+            value
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+                .trim_matches('_')
+                .to_lowercase()
+        };
+
+        let mut readable_key = sanitize(key);
+        readable_key.truncate(MAX_KEY_LENGTH);
+
+        let hash = Sha256::digest(key.as_bytes());
+        let short_hash: String = hash[..4].iter().map(|b| format!("{:02x}", b)).collect();
+
+        self.dir
+            .join(sanitize(source))
+            .join(format!("{}-{}.json", readable_key, short_hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir() -> String {
+        std::env::temp_dir()
+            .join(format!("fixtures-{:016x}", fastrand::u64(..)))
+            .display()
+            .to_string()
+    }
+
+    async fn record(dir: &str, status: StatusCode, body: &str) -> Response {
+        let recorder = FixtureStore::new(FixtureMode::Record, dir);
+        let body = String::from(body);
+
+        recorder
+            .through("test", "/tokens/mint", async {
+                Ok(FixtureStore::response(status, body))
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn replay(dir: &str) -> Result<Response, AppError> {
+        FixtureStore::new(FixtureMode::Replay, dir)
+            .through("test", "/tokens/mint", async {
+                panic!("replay must not fetch")
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_error_responses() {
+        let dir = fixture_dir();
+
+        let recorded = record(&dir, StatusCode::TOO_MANY_REQUESTS, "slow down").await;
+        assert_eq!(recorded.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(recorded.text().await.unwrap(), "slow down");
+
+        let replayed = replay(&dir).await.unwrap();
+        assert_eq!(replayed.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(replayed.text().await.unwrap(), "slow down");
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn success_replaces_a_recorded_error() {
+        let dir = fixture_dir();
+
+        record(&dir, StatusCode::SERVICE_UNAVAILABLE, "down").await;
+        record(&dir, StatusCode::OK, "{}").await;
+
+        let replayed = replay(&dir).await.unwrap();
+        assert_eq!(replayed.status(), StatusCode::OK);
+        assert_eq!(replayed.text().await.unwrap(), "{}");
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_fixture_is_not_found() {
+        let error = replay(&fixture_dir()).await.unwrap_err();
+
+        assert!(matches!(error, AppError::Api(ApiError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,6 @@
+use std::time::Duration;
+
+pub const JUPITER_BASE_URL: &str = "https://lite-api.jup.ag/tokens/v2";
+
+// Search results carry live price and trading stats.
+pub const TOKEN_INFO_CACHE_TTL: Duration = Duration::from_secs(10);
@@ -1,71 +1,251 @@
+use super::constants::TOKEN_INFO_CACHE_TTL;
 use super::models::TokenData;
-use crate::api::errors::{ApiError, ApiErrorData, JupiterApiErrorData};
-use anyhow::Error;
+use crate::api::cache::{CacheStats, ResponseCache};
+use crate::api::circuit_breaker::CircuitBreaker;
+use crate::api::errors::{ApiError, ApiErrorData, JupiterApiErrorData, non_json_error_body};
+use crate::api::fixtures::FixtureStore;
+use crate::api::models::{
+    SymbolMatch, TokenHolderInfo, TokenLinks, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo,
+    TokenStats1H, TokenStats24H,
+};
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::retry::RetryPolicy;
+use crate::api::traits::TokenDataProvider;
+use crate::errors::AppError;
+use crate::utils::decimal;
+use crate::utils::single_flight::{SharedError, SingleFlight};
+use async_trait::async_trait;
 use reqwest::{Client, Method, Response, StatusCode};
 use serde_json::from_str;
+use std::{sync::Arc, time::Duration};
 
 pub struct JupiterApiClient {
     client: Client,
     base_url: String,
+    cache: ResponseCache,
+    in_flight: SingleFlight<Result<String, SharedError>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    fixtures: Arc<FixtureStore>,
 }
 
 impl JupiterApiClient {
-    pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-            base_url: String::from("https://lite-api.jup.ag/tokens/v2"),
-        }
+    pub fn new(
+        base_url: &str,
+        retry_policy: RetryPolicy,
+        rate_limiter: Arc<RateLimiter>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        fixtures: Arc<FixtureStore>,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            client: Client::builder()
+                .timeout(retry_policy.attempt_timeout)
+                .build()?,
+            base_url: String::from(base_url.trim_end_matches('/')),
+            cache: ResponseCache::new(),
+            in_flight: SingleFlight::new(),
+            retry_policy,
+            rate_limiter,
+            circuit_breaker,
+            fixtures,
+        })
     }
 
-    pub async fn fetch_token_info(&self, token_address: &str) -> Result<TokenData, Error> {
-        let url = format!("{}/search?query={}", &self.base_url, token_address);
+    /// Search is fuzzy, so only a result for exactly this mint counts.
+    pub async fn fetch_token_info(&self, token_address: &str) -> Result<TokenData, AppError> {
+        self.search(token_address)
+            .await?
+            .into_iter()
+            .find(|token_data| token_data.id == token_address)
+            .ok_or_else(|| Self::not_found("Token not found"))
+    }
 
-        let mut response = self.client.request(Method::GET, url).send().await?;
-        response = self.handle_response(response).await?;
+    /// Tokens trading under `symbol`, in the order Jupiter ranks them.
+    pub async fn search_symbol(&self, symbol: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        self.search(symbol)
+            .await?
+            .into_iter()
+            .filter(|token_data| token_data.symbol.eq_ignore_ascii_case(symbol))
+            .map(|token_data| {
+                Ok(SymbolMatch {
+                    mint: token_data.id,
+                    name: token_data.name,
+                    symbol: token_data.symbol,
+                    fully_diluted_value: decimal::from_opt_f64(token_data.fdv)?,
+                    liquidity_usd: decimal::from_opt_f64(token_data.liquidity)?,
+                    scanned_in_guild: false,
+                })
+            })
+            .collect()
+    }
 
-        let body = response.text().await?;
+    async fn search(&self, query: &str) -> Result<Vec<TokenData>, AppError> {
+        let url = format!("{}/search?query={}", &self.base_url, query);
+
+        let body = self.get(url, TOKEN_INFO_CACHE_TTL).await?;
+
+        Ok(from_str(&body)?)
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
 
-        let token_info: Vec<TokenData> = from_str(&body)?;
-
-        if token_info.is_empty() {
-            return Err(ApiError::NotFound {
-                error_data: ApiErrorData {
-                    source: String::from("Jupiter Api"),
-                    status_code: StatusCode::NOT_FOUND.as_u16(),
-                    message: String::from("Token not found"),
-                },
-            }
-            .into());
+    /// Serves `url` from the response cache. On a miss, concurrent callers for
+    /// the same URL share one request whose body is cached for `ttl`.
+    async fn get(&self, url: String, ttl: Duration) -> Result<String, AppError> {
+        if let Some(body) = self.cache.get(&url) {
+            return Ok(body);
         }
 
-        Ok(token_info.first().unwrap().clone())
+        let body = self
+            .in_flight
+            .run(&url, async {
+                self.circuit_breaker
+                    .run(self.fetch(&url, ttl))
+                    .await
+                    .map_err(SharedError::from)
+            })
+            .await?;
+
+        Ok(body)
+    }
+
+    async fn fetch(&self, url: &str, ttl: Duration) -> Result<String, AppError> {
+        let endpoint = url.strip_prefix(&self.base_url).unwrap_or(url);
+        let response = self
+            .fixtures
+            .through("jupiter", endpoint, async {
+                Ok(self
+                    .retry_policy
+                    .send("Jupiter Api", &self.rate_limiter, || {
+                        self.client.request(Method::GET, url)
+                    })
+                    .await?)
+            })
+            .await?;
+        let response = self.handle_response(response).await?;
+        let body = response.text().await?;
+
+        self.cache.insert(String::from(url), body.clone(), ttl);
+
+        Ok(body)
     }
 
-    async fn handle_response(&self, response: Response) -> Result<Response, Error> {
+    async fn handle_response(&self, response: Response) -> Result<Response, AppError> {
         let status_code = response.status().as_u16();
 
         if status_code == 200 {
             return Ok(response);
         }
 
-        let jupiter_error_data: JupiterApiErrorData = from_str(&response.text().await?)?;
+        let body = response.text().await?;
 
         let error_data = ApiErrorData {
             source: String::from("Jupiter Api"),
             status_code,
-            message: jupiter_error_data.error,
+            message: match from_str::<JupiterApiErrorData>(&body) {
+                Ok(jupiter_error_data) => jupiter_error_data.error,
+                Err(_) => non_json_error_body(&body),
+            },
         };
 
-        match status_code {
-            400 => Err(ApiError::BadRequest { error_data }.into()),
-            401 => Err(ApiError::Unauthorized { error_data }.into()),
-            404 => Err(ApiError::NotFound { error_data }.into()),
-            429 => Err(ApiError::TooManyRequests { error_data }.into()),
-            500 => Err(ApiError::InternalServerError { error_data }.into()),
-            502 => Err(ApiError::BadGateway { error_data }.into()),
-            503 => Err(ApiError::ServiceUnavailable { error_data }.into()),
-            504 => Err(ApiError::GatewayTimeout { error_data }.into()),
-            _ => Err(ApiError::Unknown { error_data }.into()),
+        Err(ApiError::from_status(error_data).into())
+    }
+
+    fn not_found(message: &str) -> AppError {
+        ApiError::NotFound {
+            error_data: ApiErrorData {
+                source: String::from("Jupiter Api"),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+                message: String::from(message),
+            },
         }
+        .into()
+    }
+}
+
+#[async_trait]
+impl TokenDataProvider for JupiterApiClient {
+    fn name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError> {
+        let token_data = self.fetch_token_info(token_address).await?;
+
+        Ok(TokenMetadataInfo {
+            mint: token_data.id,
+            name: token_data.name,
+            symbol: token_data.symbol,
+            logo: token_data.icon.unwrap_or_default(),
+            fully_diluted_value: decimal::from_opt_f64(token_data.fdv)?,
+            links: TokenLinks {
+                discord: None,
+                telegram: token_data.telegram,
+                reddit: None,
+                twitter: token_data.twitter,
+                website: token_data.website,
+            },
+        })
+    }
+
+    async fn fetch_primary_pair(&self, token_address: &str) -> Result<TokenPairInfo, AppError> {
+        let token_data = self.fetch_token_info(token_address).await?;
+        let first_pool = token_data
+            .first_pool
+            .ok_or_else(|| Self::not_found("First pool not available"))?;
+
+        Ok(TokenPairInfo {
+            mint: token_data.id,
+            pair_address: first_pool.id,
+            exchange_name: String::from("Jupiter"),
+        })
+    }
+
+    /// Jupiter only serves stats aggregated over all pools of the token, so only
+    /// the mint and exchange name of the pair are used.
+    async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        let token_data = self.fetch_token_info(&token_pair.mint).await?;
+        let stats_1h = token_data.stats_1h.unwrap_or_default();
+        let stats_24h = token_data.stats_24h.unwrap_or_default();
+
+        Ok(TokenPairStatsInfo {
+            exchange_name: token_pair.exchange_name.clone(),
+            usd_price: decimal::from_f64(
+                token_data
+                    .usd_price
+                    .ok_or_else(|| Self::not_found("USD price not available"))?,
+            )?,
+            liquidity_usd: decimal::from_f64(token_data.liquidity.unwrap_or_default())?,
+            stats_1h: TokenStats1H {
+                buys: stats_1h.num_buys.unwrap_or_default(),
+                sells: stats_1h.num_sells.unwrap_or_default(),
+                volume: stats_1h.buy_volume.unwrap_or_default()
+                    + stats_1h.sell_volume.unwrap_or_default(),
+                price_percent_change: stats_1h.price_change.unwrap_or_default(),
+            },
+            stats_24h: TokenStats24H {
+                price_percent_change: stats_24h.price_change.unwrap_or_default(),
+            },
+            pair_created_at: token_data
+                .first_pool
+                .map(|first_pool| first_pool.created_at),
+        })
+    }
+
+    async fn fetch_holders(&self, token_address: &str) -> Result<TokenHolderInfo, AppError> {
+        let token_data = self.fetch_token_info(token_address).await?;
+        let holder_count = token_data
+            .holder_count
+            .ok_or_else(|| Self::not_found("Holder count not available"))?;
+
+        Ok(TokenHolderInfo {
+            total_holders: holder_count.max(0) as u32,
+        })
     }
 }
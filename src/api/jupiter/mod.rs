@@ -1,2 +1,3 @@
+pub mod constants;
 pub mod jupiter_api_client;
 pub mod models;
@@ -10,12 +10,31 @@ pub struct TokenData {
     pub name: String,
     #[validate(length(min = 1))]
     pub symbol: String,
+    pub icon: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
     pub dev: Option<String>,
     pub launchpad: Option<String>,
     #[serde(rename = "holderCount")]
     pub holder_count: Option<i32>,
     #[serde(rename = "firstPool")]
     pub first_pool: Option<FirstPool>,
+    pub fdv: Option<f64>,
+    #[serde(rename = "usdPrice")]
+    pub usd_price: Option<f64>,
+    pub liquidity: Option<f64>,
+    #[serde(rename = "stats1h")]
+    pub stats_1h: Option<TokenTradingStats>,
+    #[serde(rename = "stats24h")]
+    pub stats_24h: Option<TokenTradingStats>,
+    pub audit: Option<TokenAudit>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TokenAudit {
+    #[serde(rename = "devMints")]
+    pub dev_mints: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -24,3 +43,17 @@ pub struct FirstPool {
     #[serde(rename = "createdAt")]
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TokenTradingStats {
+    #[serde(rename = "priceChange")]
+    pub price_change: Option<f64>,
+    #[serde(rename = "buyVolume")]
+    pub buy_volume: Option<f64>,
+    #[serde(rename = "sellVolume")]
+    pub sell_volume: Option<f64>,
+    #[serde(rename = "numBuys")]
+    pub num_buys: Option<u32>,
+    #[serde(rename = "numSells")]
+    pub num_sells: Option<u32>,
+}
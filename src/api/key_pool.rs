@@ -0,0 +1,280 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
+
+struct ApiKeyState {
+    key: String,
+    parked_until: Option<Instant>,
+    /// Rejected as invalid, never used again.
+    disabled: bool,
+    requests: u64,
+    throttled: u64,
+    exhausted: u64,
+}
+
+impl ApiKeyState {
+    fn is_usable(&self, now: Instant) -> bool {
+        !self.disabled
+            && self
+                .parked_until
+                .is_none_or(|parked_until| parked_until <= now)
+    }
+}
+
+/// Usage counters of a single key, with the key itself masked.
+#[derive(Debug, Clone)]
+pub struct ApiKeyUsage {
+    pub label: String,
+    pub requests: u64,
+    pub throttled: u64,
+    pub exhausted: u64,
+    pub parked_for: Option<Duration>,
+    pub disabled: bool,
+}
+
+/// A set of API keys for one upstream. The current key is used until it gets
+/// throttled or runs out of quota, then it is parked and the next one takes over.
+/// Keys rejected as invalid are disabled for good.
+pub struct ApiKeyPool {
+    keys: Mutex<Vec<ApiKeyState>>,
+    current: AtomicUsize,
+}
+
+impl ApiKeyPool {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self {
+            keys: Mutex::new(
+                keys.into_iter()
+                    .map(|key| ApiKeyState {
+                        key,
+                        parked_until: None,
+                        disabled: false,
+                        requests: 0,
+                        throttled: 0,
+                        exhausted: 0,
+                    })
+                    .collect(),
+            ),
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.lock().unwrap().len()
+    }
+
+    /// Returns the index and value of the first usable key, starting from the
+    /// current one, or `None` if every key is parked or disabled.
+    pub fn acquire(&self) -> Option<(usize, String)> {
+        let mut keys = self.keys.lock().unwrap();
+        let now = Instant::now();
+        let start = self.current.load(Ordering::Relaxed);
+
+        for offset in 0..keys.len() {
+            let index = (start + offset) % keys.len();
+            let state = &mut keys[index];
+
+            if state.is_usable(now) {
+                state.parked_until = None;
+                state.requests += 1;
+                self.current.store(index, Ordering::Relaxed);
+                return Some((index, state.key.clone()));
+            }
+        }
+
+        None
+    }
+
+    /// Parks a key that hit a short-term rate limit.
+    pub fn park_throttled(&self, index: usize, duration: Duration) {
+        let mut keys = self.keys.lock().unwrap();
+        keys[index].throttled += 1;
+        keys[index].parked_until = Some(Instant::now() + duration);
+        self.current
+            .store((index + 1) % keys.len(), Ordering::Relaxed);
+    }
+
+    /// Parks a key whose quota is used up until the quota resets.
+    pub fn park_exhausted(&self, index: usize, duration: Duration) {
+        let mut keys = self.keys.lock().unwrap();
+        keys[index].exhausted += 1;
+        keys[index].parked_until = Some(Instant::now() + duration);
+        self.current
+            .store((index + 1) % keys.len(), Ordering::Relaxed);
+    }
+
+    /// Disables a key the upstream rejected as invalid.
+    pub fn disable(&self, index: usize) {
+        let mut keys = self.keys.lock().unwrap();
+        keys[index].disabled = true;
+        self.current
+            .store((index + 1) % keys.len(), Ordering::Relaxed);
+    }
+
+    /// Whether a key other than `index` could take over right now.
+    pub fn has_spare(&self, index: usize) -> bool {
+        let now = Instant::now();
+
+        self.keys
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .any(|(other, state)| other != index && state.is_usable(now))
+    }
+
+    pub fn all_disabled(&self) -> bool {
+        self.keys.lock().unwrap().iter().all(|state| state.disabled)
+    }
+
+    pub fn usage(&self) -> Vec<ApiKeyUsage> {
+        let now = Instant::now();
+
+        self.keys
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|state| ApiKeyUsage {
+                label: Self::mask(&state.key),
+                requests: state.requests,
+                throttled: state.throttled,
+                exhausted: state.exhausted,
+                parked_for: state
+                    .parked_until
+                    .filter(|parked_until| *parked_until > now)
+                    .map(|parked_until| parked_until - now),
+                disabled: state.disabled,
+            })
+            .collect()
+    }
+
+    fn mask(key: &str) -> String {
+        let chars: Vec<char> = key.chars().collect();
+        if chars.len() <= 8 {
+            return String::from("****");
+        }
+
+        format!(
+            "{}…{}",
+            chars[..4].iter().collect::<String>(),
+            chars[chars.len() - 4..].iter().collect::<String>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::advance;
+
+    const PARK: Duration = Duration::from_secs(60);
+
+    fn pool(count: usize) -> ApiKeyPool {
+        ApiKeyPool::new(
+            (0..count)
+                .map(|index| format!("moralis-key-{}", index))
+                .collect(),
+        )
+    }
+
+    fn acquired(pool: &ApiKeyPool) -> Option<usize> {
+        pool.acquire().map(|(index, _)| index)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sticks_to_the_current_key() {
+        let pool = pool(2);
+
+        assert_eq!(acquired(&pool), Some(0));
+        assert_eq!(acquired(&pool), Some(0));
+        assert_eq!(pool.usage()[0].requests, 2);
+        assert_eq!(pool.usage()[1].requests, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rotates_past_a_throttled_key_until_it_is_back() {
+        let pool = pool(2);
+        acquired(&pool);
+
+        pool.park_throttled(0, PARK);
+        assert_eq!(acquired(&pool), Some(1));
+        assert_eq!(pool.usage()[0].throttled, 1);
+        assert_eq!(pool.usage()[0].parked_for, Some(PARK));
+
+        pool.park_throttled(1, PARK * 2);
+        advance(PARK).await;
+        assert_eq!(acquired(&pool), Some(0));
+        assert_eq!(pool.usage()[0].parked_for, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn parks_an_exhausted_key_until_the_quota_resets() {
+        let pool = pool(1);
+        let reset = Duration::from_secs(6 * 60 * 60);
+
+        pool.park_exhausted(0, reset);
+        assert_eq!(acquired(&pool), None);
+        assert_eq!(pool.usage()[0].exhausted, 1);
+
+        advance(reset).await;
+        assert_eq!(acquired(&pool), Some(0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn returns_none_when_every_key_is_parked() {
+        let pool = pool(2);
+
+        pool.park_throttled(0, PARK);
+        pool.park_exhausted(1, PARK);
+
+        assert_eq!(acquired(&pool), None);
+        assert!(!pool.all_disabled());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn never_uses_a_disabled_key_again() {
+        let pool = pool(3);
+
+        pool.disable(0);
+        assert_eq!(acquired(&pool), Some(1));
+        assert!(pool.usage()[0].disabled);
+
+        // Rotation wraps around without landing on the disabled key.
+        pool.park_throttled(1, PARK);
+        pool.park_throttled(2, PARK);
+        advance(PARK).await;
+        assert_eq!(acquired(&pool), Some(1));
+
+        pool.disable(1);
+        pool.disable(2);
+        assert_eq!(acquired(&pool), None);
+        assert!(pool.all_disabled());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn has_spare_only_with_another_usable_key() {
+        let single = pool(1);
+        assert!(!single.has_spare(0));
+
+        let pool = pool(3);
+        assert!(pool.has_spare(0));
+
+        pool.park_throttled(1, PARK);
+        pool.disable(2);
+        assert!(!pool.has_spare(0));
+
+        advance(PARK).await;
+        assert!(pool.has_spare(0));
+    }
+
+    #[test]
+    fn masks_keys() {
+        assert_eq!(ApiKeyPool::mask("short"), "****");
+        assert_eq!(ApiKeyPool::mask("abcd1234efgh"), "abcd…efgh");
+    }
+}
@@ -1,4 +1,14 @@
+pub mod cache;
+pub mod circuit_breaker;
+pub mod dexscreener;
 pub mod errors;
+pub mod fallback_provider;
+pub mod fixtures;
 pub mod jupiter;
+pub mod key_pool;
 pub mod models;
 pub mod moralis;
+pub mod rate_limiter;
+pub mod retry;
+pub mod solana_rpc;
+pub mod traits;
@@ -1,7 +1,68 @@
+use super::solana_rpc::accounts::{Token2022Extensions, TokenProgram};
+use crate::errors::ChainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
 use validator::Validate;
 
+/// Chains tokens are scanned on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chain {
+    Solana,
+    Ethereum,
+    Base,
+    Bsc,
+    Arbitrum,
+}
+
+impl Chain {
+    /// EVM chains in the order they are probed when a message does not name one.
+    pub const EVM: [Chain; 4] = [Chain::Base, Chain::Ethereum, Chain::Bsc, Chain::Arbitrum];
+
+    /// Identifier stored in the DB, also the `chain` parameter of Moralis.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Solana => "solana",
+            Self::Ethereum => "eth",
+            Self::Base => "base",
+            Self::Bsc => "bsc",
+            Self::Arbitrum => "arbitrum",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Solana => "Solana",
+            Self::Ethereum => "Ethereum",
+            Self::Base => "Base",
+            Self::Bsc => "BSC",
+            Self::Arbitrum => "Arbitrum",
+        }
+    }
+}
+
+/// Accepts the DB identifiers and the names people use in chat.
+impl FromStr for Chain {
+    type Err = ChainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "solana" | "sol" => Ok(Self::Solana),
+            "eth" | "ethereum" => Ok(Self::Ethereum),
+            "base" => Ok(Self::Base),
+            "bsc" | "bnb" => Ok(Self::Bsc),
+            "arbitrum" | "arb" => Ok(Self::Arbitrum),
+            other => Err(ChainError::Unknown {
+                name: String::from(other),
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Validate)]
 pub struct SolTokenInfo {
+    pub chain: Chain,
+    /// Mint on Solana, contract address on EVM chains.
     #[validate(length(min = 32, max = 44))]
     pub mint: String,
     #[validate(length(min = 1))]
@@ -10,22 +71,87 @@ pub struct SolTokenInfo {
     pub symbol: String,
     #[validate(url)]
     pub logo: String,
-    pub links: TokenLinks,
+    /// `None` when only on-chain metadata could be fetched.
+    pub links: Option<TokenLinks>,
     pub dev: Option<String>,
     pub launchpad: Option<String>,
-    pub token_pair_exchange_name: String,
-    pub holder_count: u32,
-    #[validate(range(min = 0.0))]
-    pub fully_diluted_value: f64,
-    #[validate(range(min = 0.0))]
-    pub usd_price: f64,
-    #[validate(range(min = 0.0))]
-    pub liquidity_usd: f64,
-    pub stats_1h: TokenStats1H,
-    pub stats_24h: TokenStats24H,
+    pub holder_count: Option<u32>,
+    /// `None` when neither a price nor an upstream FDV is known.
+    pub fully_diluted_value: Option<Decimal>,
+    /// `None` when no pool stats could be fetched, including tokens still on
+    /// the bonding curve.
+    pub pair_stats: Option<TokenPairStatsInfo>,
+    pub launched_at: Option<DateTime<Utc>>,
+    pub is_fresh: bool,
+    pub authorities: Option<TokenAuthorities>,
+    pub token_program: Option<TokenProgram>,
+    pub token_extensions: Option<Token2022Extensions>,
+    pub metadata_authority: Option<MetadataAuthority>,
+    pub bonding_curve: Option<BondingCurveInfo>,
+    pub holder_concentration: Option<HolderConcentration>,
+    pub liquidity_lock: Option<LiquidityLock>,
+    pub snipers: Option<SniperReport>,
+    pub dev_profile: Option<DevProfile>,
+    /// Sections left empty because an upstream was down or too slow.
+    pub unavailable_sections: Vec<&'static str>,
+    /// Slower sections are still being fetched and will be edited in.
+    pub details_pending: bool,
 }
 
+/// Track record of the deployer wallet across its launches.
 #[derive(Debug, Clone)]
+pub struct DevProfile {
+    pub launch_count: u32,
+    pub tracked_count: usize,
+    pub above_1m_count: usize,
+    pub rugged_count: usize,
+    pub best_peak_fdv: Option<Decimal>,
+    pub best_last_fdv: Option<Decimal>,
+}
+
+/// Wallets that bought within the first slots after launch.
+#[derive(Debug, Clone)]
+pub struct SniperReport {
+    pub slot_window: u64,
+    pub wallet_count: usize,
+    pub held_percent: f64,
+}
+
+/// Share of the primary pool's LP supply that is burned or locked.
+#[derive(Debug, Clone)]
+pub struct LiquidityLock {
+    pub burned_percent: f64,
+    pub locked_percent: f64,
+}
+
+/// Share of supply held by the largest wallets, pools and burns excluded.
+#[derive(Debug, Clone)]
+pub struct HolderConcentration {
+    pub top_1_percent: f64,
+    pub top_10_percent: f64,
+    /// Share of all ranked holders. The RPC ranks the 20 largest accounts,
+    /// so after pools and burn addresses are dropped `top_n` can be below 20.
+    pub top_n_percent: f64,
+    pub top_n: usize,
+}
+
+/// Pump.fun bonding curve figures, set while the token has not migrated yet.
+#[derive(Debug, Clone)]
+pub struct BondingCurveInfo {
+    pub progress_percent: f64,
+    pub usd_price: Decimal,
+    pub market_cap_usd: Decimal,
+    pub sol_reserves: f64,
+}
+
+/// Mint and freeze authorities read from the mint account, `None` once revoked.
+#[derive(Debug, Clone)]
+pub struct TokenAuthorities {
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct TokenLinks {
     pub discord: Option<String>,
     pub telegram: Option<String>,
@@ -38,10 +164,9 @@ pub struct TokenLinks {
 pub struct TokenStats1H {
     pub buys: u32,
     pub sells: u32,
+    /// Buy and sell volume combined, since not every source splits it.
     #[validate(range(min = 0.0))]
-    pub buy_volume: f64,
-    #[validate(range(min = 0.0))]
-    pub sell_volume: f64,
+    pub volume: f64,
     #[validate(range(min = 0.0))]
     pub price_percent_change: f64,
 }
@@ -51,3 +176,55 @@ pub struct TokenStats24H {
     #[validate(range(min = 0.0))]
     pub price_percent_change: f64,
 }
+
+/// Update authority of the Metaplex metadata and whether it can still be changed.
+#[derive(Debug, Clone)]
+pub struct MetadataAuthority {
+    pub update_authority: String,
+    pub is_mutable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenMetadataInfo {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub logo: String,
+    /// Not every source knows the price, the caller can derive it from supply.
+    pub fully_diluted_value: Option<Decimal>,
+    pub links: TokenLinks,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenPairInfo {
+    pub mint: String,
+    pub pair_address: String,
+    pub exchange_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenPairStatsInfo {
+    pub exchange_name: String,
+    pub usd_price: Decimal,
+    pub liquidity_usd: Decimal,
+    pub stats_1h: TokenStats1H,
+    pub stats_24h: TokenStats24H,
+    pub pair_created_at: Option<DateTime<Utc>>,
+}
+
+/// A token whose ticker matches a `$SYMBOL` lookup.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub fully_diluted_value: Option<Decimal>,
+    pub liquidity_usd: Option<Decimal>,
+    /// Already scanned in the guild asking, such tokens are listed first.
+    pub scanned_in_guild: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenHolderInfo {
+    pub total_holders: u32,
+}
@@ -1,3 +1,21 @@
+use std::time::Duration;
+
+pub const MORALIS_BASE_URL: &str = "https://solana-gateway.moralis.io";
+pub const MORALIS_EVM_BASE_URL: &str = "https://deep-index.moralis.io/api/v2.2";
 pub const RAYDIUM_CPMM_ADDRESS: &str = "BtGUffMEnxrzdjyC3kKAHjGMpG1UdZiVWXZUaSpUv13C";
 pub const PUMP_SWAP_ADDRESS: &str = "FuegvqQP9sXQSGT58KBBXAbNcfYWcgN2DFrUA15MtDgm";
-pub const MAX_VALID_TOKEN_PAIRS: usize = 2; // just a number that was found experimentally
\ No newline at end of file
+pub const MAX_VALID_TOKEN_PAIRS: usize = 2; // just a number that was found experimentally
+
+pub const METADATA_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+pub const PAIRS_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+pub const PAIR_STATS_CACHE_TTL: Duration = Duration::from_secs(10);
+pub const HOLDERS_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+// Tokens do not move between chains, so a resolved chain can be kept for long.
+pub const CHAIN_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub const API_KEY_HEADER: &str = "X-API-KEY";
+// How long a key that got a 429 sits out when Moralis sends no `Retry-After`.
+pub const THROTTLED_KEY_PARK: Duration = Duration::from_secs(60);
+// Moralis answers 401 both for bad keys and for keys out of compute units,
+// only the message tells them apart.
+pub const QUOTA_EXHAUSTED_MESSAGES: [&str; 2] = ["usage has been consumed", "upgrade your plan"];
@@ -1,3 +1,4 @@
 pub mod constants;
 pub mod models;
 pub mod moralis_api_client;
+pub mod moralis_evm_api_client;
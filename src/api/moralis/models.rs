@@ -17,7 +17,7 @@ pub struct TokenMetadata {
     pub links: Links,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct Links {
     pub discord: Option<String>,
     pub telegram: Option<String>,
@@ -84,26 +84,14 @@ pub struct PricePercentageChange {
 
 #[derive(Debug, Deserialize)]
 pub struct Buys {
-    #[serde(rename = "5min")]
-    pub min5: u32,
     #[serde(rename = "1h")]
     pub h1: u32,
-    #[serde(rename = "4h")]
-    pub h4: u32,
-    #[serde(rename = "24h")]
-    pub h24: u32,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Sells {
-    #[serde(rename = "5min")]
-    pub min5: u32,
     #[serde(rename = "1h")]
     pub h1: u32,
-    #[serde(rename = "4h")]
-    pub h4: u32,
-    #[serde(rename = "24h")]
-    pub h24: u32,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -143,3 +131,31 @@ pub struct TokenHolderStats {
     #[serde(rename = "totalHolders")]
     pub total_holders: u32,
 }
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct EvmTokenMetadata {
+    #[validate(length(equal = 42))]
+    pub address: String,
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub symbol: String,
+    pub logo: Option<String>,
+    pub fully_diluted_valuation: Option<String>,
+    pub links: Option<Links>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EvmTokenPairs {
+    pub pairs: Vec<EvmTokenPair>,
+}
+
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct EvmTokenPair {
+    #[validate(length(min = 1))]
+    pub exchange_name: String,
+    #[validate(length(equal = 42))]
+    pub pair_address: String,
+    pub liquidity_usd: Option<f64>,
+    pub inactive_pair: bool,
+}
@@ -1,48 +1,92 @@
-use super::constants::{MAX_VALID_TOKEN_PAIRS, PUMP_SWAP_ADDRESS, RAYDIUM_CPMM_ADDRESS};
-use crate::api::errors::{ApiError, ApiErrorData, MoralisApiErrorData};
+use super::constants::{
+    API_KEY_HEADER, HOLDERS_CACHE_TTL, MAX_VALID_TOKEN_PAIRS, METADATA_CACHE_TTL,
+    PAIR_STATS_CACHE_TTL, PAIRS_CACHE_TTL, PUMP_SWAP_ADDRESS, QUOTA_EXHAUSTED_MESSAGES,
+    RAYDIUM_CPMM_ADDRESS, THROTTLED_KEY_PARK,
+};
+use crate::api::cache::{CacheStats, ResponseCache};
+use crate::api::circuit_breaker::CircuitBreaker;
+use crate::api::errors::{ApiError, ApiErrorData, MoralisApiErrorData, non_json_error_body};
+use crate::api::fixtures::FixtureStore;
+use crate::api::key_pool::{ApiKeyPool, ApiKeyUsage};
+use crate::api::models::{
+    TokenHolderInfo, TokenLinks, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo,
+    TokenStats1H, TokenStats24H,
+};
 use crate::api::moralis::models::{
     TokenHolderStats, TokenMetadata, TokenPair, TokenPairStats, TokenPairs,
 };
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::retry::{RetryPolicy, retry_after};
+use crate::api::traits::TokenDataProvider;
 use crate::errors::TokenPairError;
-use anyhow::Error;
+use crate::utils::single_flight::{SharedError, SingleFlight};
+use crate::errors::AppError;
+use crate::utils::decimal;
+use async_trait::async_trait;
+use chrono::{Days, Utc};
 use reqwest::{
-    Client, Method, Response,
+    Client, Method, Response, StatusCode,
     header::{HeaderMap, HeaderValue},
 };
 use serde_json::from_str;
+use std::{sync::Arc, time::Duration};
+use tracing::{error, warn};
 use validator::Validate;
 
 pub struct MoralisApiClient {
     client: Client,
+    key_pool: ApiKeyPool,
     base_url: String,
+    cache: ResponseCache,
+    in_flight: SingleFlight<Result<String, SharedError>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    fixtures: Arc<FixtureStore>,
 }
 
 impl MoralisApiClient {
-    pub fn new(api_key: &str) -> Result<Self, Error> {
+    pub fn new(
+        base_url: &str,
+        api_keys: Vec<String>,
+        retry_policy: RetryPolicy,
+        rate_limiter: Arc<RateLimiter>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        fixtures: Arc<FixtureStore>,
+    ) -> Result<Self, AppError> {
         let mut headers: HeaderMap = HeaderMap::new();
         headers.insert("accept", HeaderValue::from_static("application/json"));
-        headers.insert("X-API-KEY", HeaderValue::from_str(api_key)?);
+
+        // Keys are attached per request, so reject unusable ones up front.
+        for api_key in &api_keys {
+            HeaderValue::from_str(api_key)?;
+        }
 
         Ok(Self {
-            client: Client::builder().default_headers(headers).build()?,
-            base_url: String::from("https://solana-gateway.moralis.io"),
+            client: Client::builder()
+                .default_headers(headers)
+                .timeout(retry_policy.attempt_timeout)
+                .build()?,
+            key_pool: ApiKeyPool::new(api_keys),
+            base_url: String::from(base_url.trim_end_matches('/')),
+            cache: ResponseCache::new(),
+            in_flight: SingleFlight::new(),
+            retry_policy,
+            rate_limiter,
+            circuit_breaker,
+            fixtures,
         })
     }
 
-    pub async fn get_token_metadata(&self, token_address: &str) -> Result<TokenMetadata, Error> {
-        let mut response = self
-            .client
-            .request(
-                Method::GET,
-                format!("{}/token/mainnet/{}/metadata", self.base_url, token_address),
+    pub async fn get_token_metadata(&self, token_address: &str) -> Result<TokenMetadata, AppError> {
+        let body = self
+            .get(
+                &self.base_url,
+                &format!("/token/mainnet/{}/metadata", token_address),
+                METADATA_CACHE_TTL,
             )
-            .send()
             .await?;
 
-        response = self.handle_response(response).await?;
-
-        let body = response.text().await?;
-
         let token_metadata: TokenMetadata = from_str(&body)?;
 
         token_metadata.validate()?;
@@ -53,19 +97,16 @@ impl MoralisApiClient {
     pub async fn get_primary_token_pair_by_address(
         &self,
         token_address: &str,
-    ) -> Result<TokenPair, Error> {
-        let mut response = self
-            .client
-            .request(
-                Method::GET,
-                format!("{}/token/mainnet/{}/pairs", self.base_url, token_address),
+    ) -> Result<TokenPair, AppError> {
+        let body = self
+            .get(
+                &self.base_url,
+                &format!("/token/mainnet/{}/pairs", token_address),
+                PAIRS_CACHE_TTL,
             )
-            .send()
             .await?;
 
-        response = self.handle_response(response).await?;
-
-        let token_pairs: TokenPairs = from_str(&response.text().await?)?;
+        let token_pairs: TokenPairs = from_str(&body)?;
 
         let active_token_pairs: Vec<TokenPair> = token_pairs
             .pairs
@@ -94,66 +135,286 @@ impl MoralisApiClient {
         Ok(primary_token_pair)
     }
 
-    pub async fn get_token_holders(&self, token_address: &str) -> Result<TokenHolderStats, Error> {
-        let mut response = self
-            .client
-            .request(
-                Method::GET,
-                format!("{}/token/mainnet/holders/{}", self.base_url, token_address),
+    pub async fn get_token_holders(&self, token_address: &str) -> Result<TokenHolderStats, AppError> {
+        let body = self
+            .get(
+                &self.base_url,
+                &format!("/token/mainnet/holders/{}", token_address),
+                HOLDERS_CACHE_TTL,
             )
-            .send()
             .await?;
 
-        response = self.handle_response(response).await?;
-
-        let holder_stats: TokenHolderStats = from_str(&response.text().await?)?;
+        let holder_stats: TokenHolderStats = from_str(&body)?;
 
         Ok(holder_stats)
     }
 
-    pub async fn get_token_pair_stats(&self, pair_address: &str) -> Result<TokenPairStats, Error> {
-        let mut response = self
-            .client
-            .request(
-                Method::GET,
-                format!(
-                    "{}/token/mainnet/pairs/{}/stats",
-                    self.base_url, pair_address
-                ),
+    pub async fn get_token_pair_stats(&self, pair_address: &str) -> Result<TokenPairStats, AppError> {
+        let body = self
+            .get(
+                &self.base_url,
+                &format!("/token/mainnet/pairs/{}/stats", pair_address),
+                PAIR_STATS_CACHE_TTL,
             )
-            .send()
             .await?;
 
-        response = self.handle_response(response).await?;
-
-        let token_pair_stats: TokenPairStats = from_str(&response.text().await?)?;
+        let token_pair_stats: TokenPairStats = from_str(&body)?;
         
         token_pair_stats.validate()?;
 
         Ok(token_pair_stats)
     }
 
-    async fn handle_response(&self, response: Response) -> Result<Response, Error> {
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Serves `endpoint` of the gateway at `base_url` from the response cache.
+    /// On a miss, concurrent callers for the same URL share one request whose
+    /// body is cached for `ttl`. The EVM client goes through here too, so both
+    /// gateways share keys, limits and the circuit breaker.
+    pub(crate) async fn get(
+        &self,
+        base_url: &str,
+        endpoint: &str,
+        ttl: Duration,
+    ) -> Result<String, AppError> {
+        let url = format!("{}{}", base_url, endpoint);
+
+        if let Some(body) = self.cache.get(&url) {
+            return Ok(body);
+        }
+
+        let body = self
+            .in_flight
+            .run(&url, async {
+                self.circuit_breaker
+                    .run(self.fetch(&url, endpoint, ttl))
+                    .await
+                    .map_err(SharedError::from)
+            })
+            .await?;
+
+        Ok(body)
+    }
+
+    pub fn key_usage(&self) -> Vec<ApiKeyUsage> {
+        self.key_pool.usage()
+    }
+
+    async fn fetch(&self, url: &str, endpoint: &str, ttl: Duration) -> Result<String, AppError> {
+        let response = self
+            .fixtures
+            .through("moralis", endpoint, self.send(url))
+            .await?;
+        let response = self.handle_response(response).await?;
+        let body = response.text().await?;
+
+        self.cache.insert(String::from(url), body.clone(), ttl);
+
+        Ok(body)
+    }
+
+    /// Sends the request with the current key, rotating to the next key on the
+    /// first response saying the key is throttled (429) or out of quota (401).
+    /// A 401 for any other reason means the key is invalid, it is disabled and
+    /// the next key is tried. Without another key to rotate to, a 429 is left
+    /// to the retry policy. Once every key has been tried the last rejection
+    /// is returned.
+    async fn send(&self, url: &str) -> Result<Response, AppError> {
+        let mut last_error: Option<AppError> = None;
+
+        for _ in 0..self.key_pool.len() {
+            let Some((index, api_key)) = self.key_pool.acquire() else {
+                break;
+            };
+
+            let rotate_on_throttle = self.key_pool.has_spare(index);
+            let final_statuses: &[StatusCode] = if rotate_on_throttle {
+                &[StatusCode::TOO_MANY_REQUESTS, StatusCode::UNAUTHORIZED]
+            } else {
+                &[StatusCode::UNAUTHORIZED]
+            };
+
+            let response = self
+                .retry_policy
+                .send_except("Moralis Api", &self.rate_limiter, final_statuses, || {
+                    self.client
+                        .request(Method::GET, url)
+                        .header(API_KEY_HEADER, &api_key)
+                })
+                .await?;
+
+            match response.status() {
+                StatusCode::TOO_MANY_REQUESTS if rotate_on_throttle => {
+                    let park_for = retry_after(&response).unwrap_or(THROTTLED_KEY_PARK);
+                    warn!(
+                        "Moralis Api | key #{} throttled, parked for {:?}",
+                        index, park_for
+                    );
+                    self.key_pool.park_throttled(index, park_for);
+                    last_error = Some(Self::error_from(response).await?);
+                }
+                StatusCode::UNAUTHORIZED => {
+                    let error_data = Self::error_data_from(response).await?;
+                    let message = error_data.message.to_lowercase();
+
+                    if QUOTA_EXHAUSTED_MESSAGES
+                        .iter()
+                        .any(|quota_message| message.contains(quota_message))
+                    {
+                        let park_for = Self::until_quota_reset();
+                        warn!(
+                            "Moralis Api | key #{} out of quota, parked for {:?}",
+                            index, park_for
+                        );
+                        self.key_pool.park_exhausted(index, park_for);
+                        last_error = Some(ApiError::from_status(error_data).into());
+                    } else {
+                        error!("Moralis Api | key #{} is invalid, disabled", index);
+                        self.key_pool.disable(index);
+                        last_error = Some(ApiError::InvalidAPIKey { error_data }.into());
+                    }
+                }
+                _ => return Ok(response),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| self.no_usable_key()))
+    }
+
+    fn no_usable_key(&self) -> AppError {
+        if self.key_pool.all_disabled() {
+            return ApiError::InvalidAPIKey {
+                error_data: ApiErrorData {
+                    source: String::from("Moralis Api"),
+                    status_code: StatusCode::UNAUTHORIZED.as_u16(),
+                    message: String::from("Every API key is invalid"),
+                },
+            }
+            .into();
+        }
+
+        ApiError::TooManyRequests {
+            error_data: ApiErrorData {
+                source: String::from("Moralis Api"),
+                status_code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                message: String::from("Every API key is parked"),
+            },
+        }
+        .into()
+    }
+
+    /// Moralis compute-unit quotas reset daily at midnight UTC.
+    fn until_quota_reset() -> Duration {
+        let now = Utc::now();
+        let next_midnight = now
+            .date_naive()
+            .checked_add_days(Days::new(1))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|midnight| midnight.and_utc());
+
+        next_midnight
+            .and_then(|midnight| (midnight - now).to_std().ok())
+            .unwrap_or(THROTTLED_KEY_PARK)
+    }
+
+    async fn handle_response(&self, response: Response) -> Result<Response, AppError> {
         if response.status().is_success() {
             return Ok(response);
         }
 
-        let moralis_error_data: MoralisApiErrorData = from_str(&response.text().await?)?;
+        Err(Self::error_from(response).await?)
+    }
+
+    async fn error_from(response: Response) -> Result<AppError, AppError> {
+        Ok(ApiError::from_status(Self::error_data_from(response).await?).into())
+    }
+
+    async fn error_data_from(response: Response) -> Result<ApiErrorData, AppError> {
+        let status_code = response.status().as_u16();
+        let body = response.text().await?;
 
-        let error_data: ApiErrorData = ApiErrorData {
-            status_code: moralis_error_data.status_code,
-            source: String::from("Moralis Api"),
-            message: moralis_error_data.message,
+        let error_data: ApiErrorData = match from_str::<MoralisApiErrorData>(&body) {
+            Ok(moralis_error_data) => ApiErrorData {
+                status_code: moralis_error_data.status_code,
+                source: String::from("Moralis Api"),
+                message: moralis_error_data.message,
+            },
+            Err(_) => ApiErrorData {
+                status_code,
+                source: String::from("Moralis Api"),
+                message: non_json_error_body(&body),
+            },
         };
 
-        match error_data.status_code {
-            400 => Err(ApiError::BadRequest { error_data }.into()),
-            401 => Err(ApiError::Unauthorized { error_data }.into()),
-            403 => Err(ApiError::Forbidden { error_data }.into()),
-            404 => Err(ApiError::NotFound { error_data }.into()),
-            429 => Err(ApiError::TooManyRequests { error_data }.into()),
-            500 => Err(ApiError::InternalServerError { error_data }.into()),
-            _ => Err(ApiError::Unknown { error_data }.into()),
-        }
+        Ok(error_data)
+    }
+}
+
+#[async_trait]
+impl TokenDataProvider for MoralisApiClient {
+    fn name(&self) -> &'static str {
+        "moralis"
+    }
+
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError> {
+        let token_metadata = self.get_token_metadata(token_address).await?;
+
+        Ok(TokenMetadataInfo {
+            mint: token_metadata.mint,
+            name: token_metadata.name,
+            symbol: token_metadata.symbol,
+            logo: token_metadata.logo,
+            fully_diluted_value: Some(decimal::parse(&token_metadata.fully_diluted_value)?),
+            links: TokenLinks {
+                discord: token_metadata.links.discord,
+                telegram: token_metadata.links.telegram,
+                reddit: token_metadata.links.reddit,
+                twitter: token_metadata.links.twitter,
+                website: token_metadata.links.website,
+            },
+        })
+    }
+
+    async fn fetch_primary_pair(&self, token_address: &str) -> Result<TokenPairInfo, AppError> {
+        let token_pair = self.get_primary_token_pair_by_address(token_address).await?;
+
+        Ok(TokenPairInfo {
+            mint: String::from(token_address),
+            pair_address: token_pair.pair_address,
+            exchange_name: token_pair.exchange_name,
+        })
+    }
+
+    async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        let token_pair_stats = self.get_token_pair_stats(&token_pair.pair_address).await?;
+
+        Ok(TokenPairStatsInfo {
+            exchange_name: token_pair_stats.exchange,
+            usd_price: decimal::parse(&token_pair_stats.current_usd_price)?,
+            liquidity_usd: decimal::parse(&token_pair_stats.total_iquidity_usd)?,
+            stats_1h: TokenStats1H {
+                buys: token_pair_stats.buys.h1,
+                sells: token_pair_stats.sells.h1,
+                volume: token_pair_stats.buy_volume.h1 + token_pair_stats.sell_volume.h1,
+                price_percent_change: token_pair_stats.price_percent_change.h1,
+            },
+            stats_24h: TokenStats24H {
+                price_percent_change: token_pair_stats.price_percent_change.h24,
+            },
+            pair_created_at: None,
+        })
+    }
+
+    async fn fetch_holders(&self, token_address: &str) -> Result<TokenHolderInfo, AppError> {
+        let holder_stats = self.get_token_holders(token_address).await?;
+
+        Ok(TokenHolderInfo {
+            total_holders: holder_stats.total_holders,
+        })
     }
 }
@@ -0,0 +1,335 @@
+use super::constants::{
+    CHAIN_CACHE_TTL, HOLDERS_CACHE_TTL, METADATA_CACHE_TTL, PAIR_STATS_CACHE_TTL, PAIRS_CACHE_TTL,
+};
+use super::models::{
+    EvmTokenMetadata, EvmTokenPair, EvmTokenPairs, TokenHolderStats, TokenPairStats,
+};
+use super::moralis_api_client::MoralisApiClient;
+use crate::api::cache::ResponseCache;
+use crate::api::errors::{ApiError, ApiErrorData};
+use crate::api::models::{
+    Chain, TokenHolderInfo, TokenLinks, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo,
+    TokenStats1H, TokenStats24H,
+};
+use crate::errors::{AppError, TokenPairError};
+use crate::utils::decimal;
+use futures::future::join_all;
+use reqwest::StatusCode;
+use serde_json::from_str;
+use std::{str::FromStr, sync::Arc};
+use tracing::Level;
+use validator::Validate;
+
+/// Moralis EVM gateway for Ethereum, Base, BSC and Arbitrum tokens. Requests
+/// go through the Solana gateway client, so both share keys and limits.
+pub struct MoralisEvmApiClient {
+    moralis: Arc<MoralisApiClient>,
+    base_url: String,
+    /// Chain ids by token address, so repeat scans skip probing every chain.
+    chains: ResponseCache,
+}
+
+impl MoralisEvmApiClient {
+    pub fn new(base_url: &str, moralis: Arc<MoralisApiClient>) -> Self {
+        Self {
+            moralis,
+            base_url: String::from(base_url.trim_end_matches('/')),
+            chains: ResponseCache::new(),
+        }
+    }
+
+    pub async fn get_token_metadata(
+        &self,
+        token_address: &str,
+        chain: Chain,
+    ) -> Result<EvmTokenMetadata, AppError> {
+        let body = self
+            .moralis
+            .get(
+                &self.base_url,
+                &format!(
+                    "/erc20/metadata?chain={}&addresses[0]={}",
+                    chain.id(),
+                    token_address
+                ),
+                METADATA_CACHE_TTL,
+            )
+            .await?;
+
+        let token_metadata = from_str::<Vec<EvmTokenMetadata>>(&body)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Self::not_found("Token metadata not found"))?;
+
+        token_metadata.validate()?;
+
+        Ok(token_metadata)
+    }
+
+    /// Picks the most liquid active pair.
+    pub async fn get_primary_token_pair(
+        &self,
+        token_address: &str,
+        chain: Chain,
+    ) -> Result<EvmTokenPair, AppError> {
+        let body = self
+            .moralis
+            .get(
+                &self.base_url,
+                &format!("/erc20/{}/pairs?chain={}", token_address, chain.id()),
+                PAIRS_CACHE_TTL,
+            )
+            .await?;
+
+        let token_pairs: EvmTokenPairs = from_str(&body)?;
+
+        let primary_token_pair = token_pairs
+            .pairs
+            .into_iter()
+            .filter(|pair| !pair.inactive_pair)
+            .max_by(|a, b| {
+                a.liquidity_usd
+                    .unwrap_or_default()
+                    .total_cmp(&b.liquidity_usd.unwrap_or_default())
+            })
+            .ok_or_else(|| -> AppError {
+                TokenPairError::ActivePairNotFound {
+                    token_address: String::from(token_address),
+                }
+                .into()
+            })?;
+
+        primary_token_pair.validate()?;
+
+        Ok(primary_token_pair)
+    }
+
+    pub async fn get_token_pair_stats(
+        &self,
+        pair_address: &str,
+        chain: Chain,
+    ) -> Result<TokenPairStats, AppError> {
+        let body = self
+            .moralis
+            .get(
+                &self.base_url,
+                &format!("/pairs/{}/stats?chain={}", pair_address, chain.id()),
+                PAIR_STATS_CACHE_TTL,
+            )
+            .await?;
+
+        let token_pair_stats: TokenPairStats = from_str(&body)?;
+
+        token_pair_stats.validate()?;
+
+        Ok(token_pair_stats)
+    }
+
+    pub async fn get_token_holders(
+        &self,
+        token_address: &str,
+        chain: Chain,
+    ) -> Result<TokenHolderStats, AppError> {
+        let body = self
+            .moralis
+            .get(
+                &self.base_url,
+                &format!("/erc20/{}/holders?chain={}", token_address, chain.id()),
+                HOLDERS_CACHE_TTL,
+            )
+            .await?;
+
+        let holder_stats: TokenHolderStats = from_str(&body)?;
+
+        Ok(holder_stats)
+    }
+
+    /// Finds the chain the token trades on along with its primary pair. The
+    /// chain named in the message, else the one an earlier scan resolved, is
+    /// tried alone first. Otherwise every EVM chain is asked at once and the
+    /// most liquid pair wins. Pair lookups are cached, so the scan reuses them.
+    pub async fn find_primary_pair(
+        &self,
+        token_address: &str,
+        hint: Option<Chain>,
+    ) -> Result<(Chain, TokenPairInfo), AppError> {
+        let known_chain = hint.or_else(|| self.resolved_chain(token_address));
+
+        if let Some(chain) = known_chain
+            && let Ok(token_pair) = self.get_primary_token_pair(token_address, chain).await
+        {
+            self.resolve_chain(token_address, chain);
+            return Ok((chain, Self::pair_info(token_address, token_pair)));
+        }
+
+        let chains: Vec<Chain> = Chain::EVM
+            .into_iter()
+            .filter(|chain| Some(*chain) != known_chain)
+            .collect();
+        let results = join_all(
+            chains
+                .iter()
+                .map(|chain| self.get_primary_token_pair(token_address, *chain)),
+        )
+        .await;
+
+        let mut primary: Option<(Chain, EvmTokenPair)> = None;
+        let mut errors: Vec<AppError> = vec![];
+        for (chain, result) in chains.into_iter().zip(results) {
+            match result {
+                Ok(token_pair) => {
+                    let liquidity_usd = token_pair.liquidity_usd.unwrap_or_default();
+                    if primary.as_ref().is_none_or(|(_, primary_pair)| {
+                        liquidity_usd > primary_pair.liquidity_usd.unwrap_or_default()
+                    }) {
+                        primary = Some((chain, token_pair));
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        match primary {
+            Some((chain, token_pair)) => {
+                self.resolve_chain(token_address, chain);
+                Ok((chain, Self::pair_info(token_address, token_pair)))
+            }
+            None => Err(Self::most_telling(errors)),
+        }
+    }
+
+    /// Finds the chain of a token without an active pair by the chain Moralis
+    /// has its metadata on, the first one in `Chain::EVM` order.
+    pub async fn find_chain(&self, token_address: &str) -> Result<Chain, AppError> {
+        if let Some(chain) = self.resolved_chain(token_address) {
+            return Ok(chain);
+        }
+
+        let results = join_all(
+            Chain::EVM
+                .iter()
+                .map(|chain| self.get_token_metadata(token_address, *chain)),
+        )
+        .await;
+
+        let mut errors: Vec<AppError> = vec![];
+        for (chain, result) in Chain::EVM.into_iter().zip(results) {
+            match result {
+                Ok(_) => {
+                    self.resolve_chain(token_address, chain);
+                    return Ok(chain);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Err(Self::most_telling(errors))
+    }
+
+    pub async fn fetch_metadata(
+        &self,
+        token_address: &str,
+        chain: Chain,
+    ) -> Result<TokenMetadataInfo, AppError> {
+        let token_metadata = self.get_token_metadata(token_address, chain).await?;
+        let links = token_metadata.links.unwrap_or_default();
+
+        Ok(TokenMetadataInfo {
+            mint: token_metadata.address,
+            name: token_metadata.name,
+            symbol: token_metadata.symbol,
+            logo: token_metadata.logo.unwrap_or_default(),
+            fully_diluted_value: token_metadata
+                .fully_diluted_valuation
+                .as_deref()
+                .map(decimal::parse)
+                .transpose()?,
+            links: TokenLinks {
+                discord: links.discord,
+                telegram: links.telegram,
+                reddit: links.reddit,
+                twitter: links.twitter,
+                website: links.website,
+            },
+        })
+    }
+
+    pub async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+        chain: Chain,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        let token_pair_stats = self
+            .get_token_pair_stats(&token_pair.pair_address, chain)
+            .await?;
+
+        Ok(TokenPairStatsInfo {
+            exchange_name: token_pair_stats.exchange,
+            usd_price: decimal::parse(&token_pair_stats.current_usd_price)?,
+            liquidity_usd: decimal::parse(&token_pair_stats.total_iquidity_usd)?,
+            stats_1h: TokenStats1H {
+                buys: token_pair_stats.buys.h1,
+                sells: token_pair_stats.sells.h1,
+                volume: token_pair_stats.buy_volume.h1 + token_pair_stats.sell_volume.h1,
+                price_percent_change: token_pair_stats.price_percent_change.h1,
+            },
+            stats_24h: TokenStats24H {
+                price_percent_change: token_pair_stats.price_percent_change.h24,
+            },
+            pair_created_at: None,
+        })
+    }
+
+    pub async fn fetch_holders(
+        &self,
+        token_address: &str,
+        chain: Chain,
+    ) -> Result<TokenHolderInfo, AppError> {
+        let holder_stats = self.get_token_holders(token_address, chain).await?;
+
+        Ok(TokenHolderInfo {
+            total_holders: holder_stats.total_holders,
+        })
+    }
+
+    fn pair_info(token_address: &str, token_pair: EvmTokenPair) -> TokenPairInfo {
+        TokenPairInfo {
+            mint: String::from(token_address),
+            pair_address: token_pair.pair_address,
+            exchange_name: token_pair.exchange_name,
+        }
+    }
+
+    fn resolved_chain(&self, token_address: &str) -> Option<Chain> {
+        let chain = self.chains.get(&token_address.to_lowercase())?;
+
+        Chain::from_str(&chain).ok()
+    }
+
+    fn resolve_chain(&self, token_address: &str, chain: Chain) {
+        self.chains.insert(
+            token_address.to_lowercase(),
+            String::from(chain.id()),
+            CHAIN_CACHE_TTL,
+        );
+    }
+
+    /// An outage on one chain says more than "not found" on the others.
+    fn most_telling(mut errors: Vec<AppError>) -> AppError {
+        match errors.iter().position(|e| e.severity() != Level::INFO) {
+            Some(index) => errors.swap_remove(index),
+            None => errors.swap_remove(0),
+        }
+    }
+
+    fn not_found(message: &str) -> AppError {
+        ApiError::NotFound {
+            error_data: ApiErrorData {
+                source: String::from("Moralis Api"),
+                status_code: StatusCode::NOT_FOUND.as_u16(),
+                message: String::from(message),
+            },
+        }
+        .into()
+    }
+}
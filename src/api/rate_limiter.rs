@@ -0,0 +1,241 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    select,
+    sync::Notify,
+    time::{Instant, sleep},
+};
+
+/// Slowest sustained rate accepted, lower rates would park callers for hours
+/// and overflow the wait computation.
+pub const MIN_PER_SECOND: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Sustained requests per second, `0` disables limiting.
+    pub per_second: f64,
+    /// Requests that may be sent back to back after an idle period.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Clamps the rate to `MIN_PER_SECOND`, treating zero, negative and
+    /// non-finite rates as disabled, and the burst to at least one request.
+    pub fn clamped(self) -> Self {
+        Self {
+            per_second: if self.per_second > 0.0 && self.per_second.is_finite() {
+                self.per_second.max(MIN_PER_SECOND)
+            } else {
+                0.0
+            },
+            burst: self.burst.max(1),
+        }
+    }
+}
+
+struct Bucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst as f64);
+        self.refilled_at = now;
+    }
+}
+
+/// Token bucket shared by every request to one upstream. Callers wait for a
+/// token in arrival order instead of hitting the upstream's own throttling.
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+    queue: tokio::sync::Mutex<()>,
+    /// Wakes the waiting caller when the limit changes.
+    limit_changed: Notify,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        let limit = limit.clamped();
+        Self {
+            bucket: Mutex::new(Bucket {
+                limit,
+                tokens: limit.burst as f64,
+                refilled_at: Instant::now(),
+            }),
+            queue: tokio::sync::Mutex::new(()),
+            limit_changed: Notify::new(),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        let _turn = self.queue.lock().await;
+
+        loop {
+            // Registered before the bucket is read, so a change made while we
+            // compute the wait is not missed.
+            let limit_changed = self.limit_changed.notified();
+            tokio::pin!(limit_changed);
+            limit_changed.as_mut().enable();
+
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                if bucket.limit.per_second <= 0.0 {
+                    return;
+                }
+
+                bucket.refill(Instant::now());
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.limit.per_second)
+            };
+
+            select! {
+                _ = sleep(wait) => {}
+                _ = limit_changed => {}
+            }
+        }
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.bucket.lock().unwrap().limit
+    }
+
+    pub fn set_limit(&self, limit: RateLimit) {
+        let limit = limit.clamped();
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill(Instant::now());
+        bucket.limit = limit;
+        bucket.tokens = bucket.tokens.min(limit.burst as f64);
+        drop(bucket);
+
+        self.limit_changed.notify_waiters();
+    }
+}
+
+/// The limiter of every upstream, kept for runtime adjustments.
+pub struct RateLimiters {
+    pub moralis: Arc<RateLimiter>,
+    pub jupiter: Arc<RateLimiter>,
+    pub dexscreener: Arc<RateLimiter>,
+    pub solana_rpc: Arc<RateLimiter>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(per_second: f64, burst: u32) -> RateLimiter {
+        RateLimiter::new(RateLimit { per_second, burst })
+    }
+
+    #[test]
+    fn clamps_rates() {
+        let slow = limiter(1e-300, 0).limit();
+        assert_eq!(slow.per_second, MIN_PER_SECOND);
+        assert_eq!(slow.burst, 1);
+
+        for per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(limiter(per_second, 1).limit().per_second, 0.0);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slowest_rate_waits_without_overflow() {
+        let rate_limiter = limiter(1.0, 1);
+        rate_limiter.set_limit(RateLimit {
+            per_second: f64::MIN_POSITIVE,
+            burst: 1,
+        });
+
+        let start = Instant::now();
+        rate_limiter.acquire().await;
+        rate_limiter.acquire().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_at_the_new_rate_after_set_limit() {
+        let rate_limiter = limiter(1.0, 2);
+        rate_limiter.acquire().await;
+        rate_limiter.acquire().await;
+
+        rate_limiter.set_limit(RateLimit {
+            per_second: 10.0,
+            burst: 2,
+        });
+
+        let start = Instant::now();
+        rate_limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        // Idle time refills up to the burst, not beyond it.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        let start = Instant::now();
+        for _ in 0..3 {
+            rate_limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lowered_burst_drops_saved_tokens() {
+        let rate_limiter = limiter(1.0, 5);
+        rate_limiter.set_limit(RateLimit {
+            per_second: 1.0,
+            burst: 1,
+        });
+
+        let start = Instant::now();
+        rate_limiter.acquire().await;
+        rate_limiter.acquire().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limit_change_reaches_a_waiting_caller() {
+        let rate_limiter = Arc::new(limiter(MIN_PER_SECOND, 1));
+        rate_limiter.acquire().await;
+
+        let start = Instant::now();
+        let waiter = {
+            let rate_limiter = Arc::clone(&rate_limiter);
+            tokio::spawn(async move { rate_limiter.acquire().await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        rate_limiter.set_limit(RateLimit {
+            per_second: 0.0,
+            burst: 1,
+        });
+        waiter.await.unwrap();
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn disabled_limit_never_waits() {
+        let rate_limiter = limiter(1.0, 1);
+        rate_limiter.set_limit(RateLimit {
+            per_second: 0.0,
+            burst: 1,
+        });
+
+        let start = Instant::now();
+        for _ in 0..10 {
+            rate_limiter.acquire().await;
+        }
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
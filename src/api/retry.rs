@@ -0,0 +1,315 @@
+use super::rate_limiter::RateLimiter;
+use reqwest::{RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::warn;
+
+/// Retries throttled, unavailable and unreachable upstream requests with
+/// jittered exponential backoff, honoring `Retry-After` when it is sent.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one, `1` disables retries.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Timeout of a single attempt, applied by the client so a hung request
+    /// fails instead of stalling the scan.
+    pub attempt_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            attempt_timeout: Duration::from_secs(4),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sends the request built by `build_request` until it succeeds, fails with
+    /// a non-retryable outcome or the attempt budget is spent. Every attempt
+    /// waits for `rate_limiter`. The last response is returned as-is for the
+    /// client to turn into an error.
+    pub async fn send<F>(
+        &self,
+        source: &str,
+        rate_limiter: &RateLimiter,
+        build_request: F,
+    ) -> Result<Response, reqwest::Error>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        self.send_except(source, rate_limiter, &[], build_request)
+            .await
+    }
+
+    /// `send`, but responses with one of `final_statuses` are returned right
+    /// away, for callers that handle them on their own (e.g. by switching keys).
+    pub async fn send_except<F>(
+        &self,
+        source: &str,
+        rate_limiter: &RateLimiter,
+        final_statuses: &[StatusCode],
+        build_request: F,
+    ) -> Result<Response, reqwest::Error>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut attempt = 1;
+
+        loop {
+            rate_limiter.acquire().await;
+            let result = build_request().send().await;
+
+            if attempt >= self.max_attempts {
+                return result;
+            }
+
+            let delay = match &result {
+                Ok(response)
+                    if Self::is_retryable_status(response.status())
+                        && !final_statuses.contains(&response.status()) =>
+                {
+                    match retry_after(response) {
+                        // Waiting longer than we are willing to would stall the scan.
+                        Some(retry_after) if retry_after > self.max_delay => return result,
+                        Some(retry_after) => retry_after,
+                        None => self.backoff(attempt),
+                    }
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => self.backoff(attempt),
+                _ => return result,
+            };
+
+            match &result {
+                Ok(response) => warn!(
+                    "{} | attempt {} failed with {}, retrying in {:?}",
+                    source,
+                    attempt,
+                    response.status(),
+                    delay
+                ),
+                Err(e) => warn!(
+                    "{} | attempt {} failed: {}, retrying in {:?}",
+                    source, attempt, e, delay
+                ),
+            }
+
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Full jitter: a random delay up to the exponential backoff for `attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+
+        exponential.mul_f64(fastrand::f64())
+    }
+}
+
+/// Reads the `Retry-After` header. Only the delay-seconds form is supported.
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::rate_limiter::RateLimit;
+    use reqwest::Client;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(50),
+            attempt_timeout: Duration::from_secs(1),
+        }
+    }
+
+    fn unlimited() -> RateLimiter {
+        RateLimiter::new(RateLimit {
+            per_second: 0.0,
+            burst: 1,
+        })
+    }
+
+    /// Answers the n-th request with the n-th canned response (the last one
+    /// repeats) and counts the requests it got.
+    async fn serve(responses: &'static [&'static str]) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buffer = [0u8; 1024];
+                let _ = stream.read(&mut buffer).await;
+
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let status_line = responses[n.min(responses.len() - 1)];
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status_line
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    async fn send(
+        policy: RetryPolicy,
+        final_statuses: &[StatusCode],
+        responses: &'static [&'static str],
+    ) -> (StatusCode, usize) {
+        let (url, requests) = serve(responses).await;
+        let client = Client::new();
+
+        let response = policy
+            .send_except("test", &unlimited(), final_statuses, || client.get(&url))
+            .await
+            .unwrap();
+
+        (response.status(), requests.load(Ordering::SeqCst))
+    }
+
+    #[test]
+    fn backoff_stays_within_the_capped_exponential() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            attempt_timeout: Duration::from_secs(1),
+        };
+
+        for attempt in 1..=10 {
+            let bound = (policy.base_delay * 2u32.pow(attempt - 1)).min(policy.max_delay);
+            for _ in 0..100 {
+                assert!(policy.backoff(attempt) <= bound);
+            }
+        }
+    }
+
+    #[test]
+    fn backoff_does_not_overflow_on_late_attempts() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.backoff(u32::MAX) <= policy.max_delay);
+    }
+
+    #[tokio::test]
+    async fn retries_unavailable_until_success() {
+        let (status, requests) = send(policy(3), &[], &["503 Service Unavailable", "200 OK"]).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(requests, 2);
+    }
+
+    #[tokio::test]
+    async fn returns_last_response_when_attempts_are_spent() {
+        let (status, requests) = send(policy(3), &[], &["502 Bad Gateway"]).await;
+
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(requests, 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let (status, requests) = send(policy(3), &[], &["404 Not Found"]).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(requests, 1);
+    }
+
+    #[tokio::test]
+    async fn honors_short_retry_after() {
+        let (status, requests) = send(
+            policy(3),
+            &[],
+            &["429 Too Many Requests\r\nRetry-After: 0", "200 OK"],
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(requests, 2);
+    }
+
+    #[tokio::test]
+    async fn gives_up_on_retry_after_beyond_max_delay() {
+        let (status, requests) = send(
+            policy(3),
+            &[],
+            &["429 Too Many Requests\r\nRetry-After: 60", "200 OK"],
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(requests, 1);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_backoff_on_http_date_retry_after() {
+        let (status, requests) = send(
+            policy(3),
+            &[],
+            &[
+                "503 Service Unavailable\r\nRetry-After: Wed, 21 Oct 2015 07:28:00 GMT",
+                "200 OK",
+            ],
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(requests, 2);
+    }
+
+    #[tokio::test]
+    async fn returns_final_statuses_right_away() {
+        let (status, requests) = send(
+            policy(3),
+            &[StatusCode::TOO_MANY_REQUESTS],
+            &["429 Too Many Requests", "200 OK"],
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(requests, 1);
+    }
+}
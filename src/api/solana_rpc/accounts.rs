@@ -0,0 +1,831 @@
+use super::constants::{
+    ACCOUNT_STATE_FROZEN, EXTENSION_DEFAULT_ACCOUNT_STATE, EXTENSION_NON_TRANSFERABLE,
+    EXTENSION_PERMANENT_DELEGATE, EXTENSION_TRANSFER_FEE_CONFIG, EXTENSION_TRANSFER_HOOK,
+    LAMPORTS_PER_SOL, METAPLEX_METADATA_V1_KEY, MINT_ACCOUNT_LEN,
+    PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES, PUMP_FUN_TOKEN_DECIMALS, RAYDIUM_AMM_V4_LP_MINT_OFFSET,
+    RAYDIUM_AMM_V4_LP_RESERVE_OFFSET, RAYDIUM_CPMM_LP_MINT_OFFSET, RAYDIUM_CPMM_LP_SUPPLY_OFFSET,
+    TOKEN_2022_ACCOUNT_TYPE_OFFSET, TOKEN_2022_MINT_ACCOUNT_TYPE,
+};
+use crate::errors::AccountError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenProgram {
+    Token,
+    Token2022,
+}
+
+/// Decoded SPL Token mint account, shared by Token and Token-2022.
+#[derive(Debug, Clone)]
+pub struct MintAccount {
+    pub token_program: TokenProgram,
+    pub mint_authority: Option<String>,
+    pub supply: u64,
+    pub decimals: u8,
+    pub freeze_authority: Option<String>,
+    pub extensions: Token2022Extensions,
+}
+
+/// Token-2022 mint extensions that can restrict or tax holders.
+#[derive(Debug, Clone, Default)]
+pub struct Token2022Extensions {
+    pub transfer_fee_basis_points: Option<u16>,
+    pub permanent_delegate: Option<String>,
+    pub transfer_hook_program: Option<String>,
+    pub non_transferable: bool,
+    pub default_account_state_frozen: bool,
+}
+
+/// `current_epoch` picks which of the two Token-2022 transfer fees applies.
+pub fn decode_mint(
+    address: &str,
+    token_program: TokenProgram,
+    data: &[u8],
+    current_epoch: u64,
+) -> Result<MintAccount, AccountError> {
+    if data.len() < MINT_ACCOUNT_LEN {
+        return Err(AccountError::InvalidData {
+            address: String::from(address),
+            reason: format!("mint account is {} bytes", data.len()),
+        });
+    }
+
+    let mut reader = AccountReader::new(address, data);
+
+    let mint_authority = reader.read_coption_pubkey()?;
+    let supply = reader.read_u64()?;
+    let decimals = reader.read_u8()?;
+    let _is_initialized = reader.read_u8()?;
+    let freeze_authority = reader.read_coption_pubkey()?;
+
+    let extensions = match token_program {
+        TokenProgram::Token => Token2022Extensions::default(),
+        TokenProgram::Token2022 => decode_mint_extensions(address, data, current_epoch)?,
+    };
+
+    Ok(MintAccount {
+        token_program,
+        mint_authority,
+        supply,
+        decimals,
+        freeze_authority,
+        extensions,
+    })
+}
+
+/// Walks the TLV entries that follow the account type byte of a Token-2022 mint.
+fn decode_mint_extensions(
+    address: &str,
+    data: &[u8],
+    current_epoch: u64,
+) -> Result<Token2022Extensions, AccountError> {
+    let mut extensions = Token2022Extensions::default();
+
+    // A mint without extensions is stored with the plain 82-byte layout.
+    if data.len() <= TOKEN_2022_ACCOUNT_TYPE_OFFSET {
+        return Ok(extensions);
+    }
+
+    let mut reader = AccountReader::new(address, &data[TOKEN_2022_ACCOUNT_TYPE_OFFSET..]);
+
+    let account_type = reader.read_u8()?;
+    if account_type != TOKEN_2022_MINT_ACCOUNT_TYPE {
+        return Err(AccountError::InvalidData {
+            address: String::from(address),
+            reason: format!("unexpected account type {}", account_type),
+        });
+    }
+
+    while reader.remaining() >= 4 {
+        let extension_type = reader.read_u16()?;
+        let length = reader.read_u16()? as usize;
+
+        // Uninitialized entries mark the zeroed tail of the account.
+        if extension_type == 0 {
+            break;
+        }
+
+        let mut value = AccountReader::new(address, reader.read_bytes(length)?);
+
+        match extension_type {
+            EXTENSION_TRANSFER_FEE_CONFIG => {
+                // Authorities and withheld amount come first, then the older
+                // and newer fee. The newer fee only applies from its epoch on.
+                value.read_bytes(32 + 32 + 8)?;
+                let older_fee = read_transfer_fee(&mut value)?;
+                let newer_fee = read_transfer_fee(&mut value)?;
+                let (_, basis_points) = if current_epoch >= newer_fee.0 {
+                    newer_fee
+                } else {
+                    older_fee
+                };
+                extensions.transfer_fee_basis_points = Some(basis_points);
+            }
+            EXTENSION_DEFAULT_ACCOUNT_STATE => {
+                extensions.default_account_state_frozen = value.read_u8()? == ACCOUNT_STATE_FROZEN;
+            }
+            EXTENSION_NON_TRANSFERABLE => {
+                extensions.non_transferable = true;
+            }
+            EXTENSION_PERMANENT_DELEGATE => {
+                extensions.permanent_delegate = value.read_optional_nonzero_pubkey()?;
+            }
+            EXTENSION_TRANSFER_HOOK => {
+                let _authority = value.read_optional_nonzero_pubkey()?;
+                extensions.transfer_hook_program = value.read_optional_nonzero_pubkey()?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(extensions)
+}
+
+/// Reads a Token-2022 `TransferFee` as its starting epoch and basis points.
+fn read_transfer_fee(reader: &mut AccountReader) -> Result<(u64, u16), AccountError> {
+    let epoch = reader.read_u64()?;
+    let _maximum_fee = reader.read_u64()?;
+    let basis_points = reader.read_u16()?;
+
+    Ok((epoch, basis_points))
+}
+
+/// SPL token account, only the fields shared by Token and Token-2022.
+#[derive(Debug, Clone)]
+pub struct TokenAccount {
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+}
+
+pub fn decode_token_account(address: &str, data: &[u8]) -> Result<TokenAccount, AccountError> {
+    let mut reader = AccountReader::new(address, data);
+
+    Ok(TokenAccount {
+        mint: reader.read_pubkey()?,
+        owner: reader.read_pubkey()?,
+        amount: reader.read_u64()?,
+    })
+}
+
+/// LP mint of a pool and the LP amount the pool believes it has issued.
+#[derive(Debug, Clone)]
+pub struct LpPool {
+    pub lp_mint: String,
+    pub lp_issued: u64,
+}
+
+pub fn decode_raydium_amm_v4_lp(address: &str, data: &[u8]) -> Result<LpPool, AccountError> {
+    decode_lp_pool(
+        address,
+        data,
+        RAYDIUM_AMM_V4_LP_MINT_OFFSET,
+        RAYDIUM_AMM_V4_LP_RESERVE_OFFSET,
+    )
+}
+
+pub fn decode_raydium_cpmm_lp(address: &str, data: &[u8]) -> Result<LpPool, AccountError> {
+    decode_lp_pool(
+        address,
+        data,
+        RAYDIUM_CPMM_LP_MINT_OFFSET,
+        RAYDIUM_CPMM_LP_SUPPLY_OFFSET,
+    )
+}
+
+fn decode_lp_pool(
+    address: &str,
+    data: &[u8],
+    lp_mint_offset: usize,
+    lp_issued_offset: usize,
+) -> Result<LpPool, AccountError> {
+    let mut reader = AccountReader::new(address, data);
+
+    reader.seek(lp_mint_offset)?;
+    let lp_mint = reader.read_pubkey()?;
+
+    reader.seek(lp_issued_offset)?;
+    let lp_issued = reader.read_u64()?;
+
+    Ok(LpPool { lp_mint, lp_issued })
+}
+
+/// Metaplex token metadata, only the fields the scan shows.
+#[derive(Debug, Clone)]
+pub struct MetaplexMetadata {
+    pub update_authority: String,
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub is_mutable: bool,
+}
+
+pub fn decode_metaplex_metadata(
+    address: &str,
+    data: &[u8],
+) -> Result<MetaplexMetadata, AccountError> {
+    let mut reader = AccountReader::new(address, data);
+
+    let key = reader.read_u8()?;
+    if key != METAPLEX_METADATA_V1_KEY {
+        return Err(AccountError::InvalidData {
+            address: String::from(address),
+            reason: format!("unexpected metadata key {}", key),
+        });
+    }
+
+    let update_authority = reader.read_pubkey()?;
+    let mint = reader.read_pubkey()?;
+    let name = reader.read_string()?;
+    let symbol = reader.read_string()?;
+    let _uri = reader.read_string()?;
+    let _seller_fee_basis_points = reader.read_u16()?;
+
+    // Creators are skipped: address, verified flag and share each.
+    if reader.read_bool()? {
+        let creator_count = reader.read_u32()? as usize;
+        reader.read_bytes(creator_count * (32 + 1 + 1))?;
+    }
+
+    let _primary_sale_happened = reader.read_bool()?;
+    let is_mutable = reader.read_bool()?;
+
+    Ok(MetaplexMetadata {
+        update_authority,
+        mint,
+        name,
+        symbol,
+        is_mutable,
+    })
+}
+
+/// Pump.fun bonding curve state of a token that has not migrated to a pool yet.
+#[derive(Debug, Clone)]
+pub struct BondingCurve {
+    pub virtual_token_reserves: u64,
+    pub virtual_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+}
+
+impl BondingCurve {
+    pub fn price_in_sol(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+
+        (self.virtual_sol_reserves as f64 / LAMPORTS_PER_SOL)
+            / (self.virtual_token_reserves as f64 / 10f64.powi(PUMP_FUN_TOKEN_DECIMALS))
+    }
+
+    pub fn market_cap_in_sol(&self) -> f64 {
+        self.price_in_sol() * self.token_total_supply as f64 / 10f64.powi(PUMP_FUN_TOKEN_DECIMALS)
+    }
+
+    pub fn real_sol_reserves_in_sol(&self) -> f64 {
+        self.real_sol_reserves as f64 / LAMPORTS_PER_SOL
+    }
+
+    /// Share of the sellable supply already bought off the curve.
+    pub fn progress_percent(&self) -> f64 {
+        if self.complete {
+            return 100.0;
+        }
+
+        let sold = PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES.saturating_sub(self.real_token_reserves);
+        (sold as f64 * 100.0 / PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES as f64).clamp(0.0, 100.0)
+    }
+}
+
+pub fn decode_bonding_curve(address: &str, data: &[u8]) -> Result<BondingCurve, AccountError> {
+    let mut reader = AccountReader::new(address, data);
+
+    let _discriminator = reader.read_bytes(8)?;
+
+    Ok(BondingCurve {
+        virtual_token_reserves: reader.read_u64()?,
+        virtual_sol_reserves: reader.read_u64()?,
+        real_token_reserves: reader.read_u64()?,
+        real_sol_reserves: reader.read_u64()?,
+        token_total_supply: reader.read_u64()?,
+        complete: reader.read_bool()?,
+    })
+}
+
+/// Little-endian cursor over raw account data.
+pub struct AccountReader<'a> {
+    address: &'a str,
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AccountReader<'a> {
+    pub fn new(address: &'a str, data: &'a [u8]) -> Self {
+        Self {
+            address,
+            data,
+            offset: 0,
+        }
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], AccountError> {
+        let end = self.offset + len;
+
+        if end > self.data.len() {
+            return Err(AccountError::InvalidData {
+                address: String::from(self.address),
+                reason: format!("unexpected end of data at offset {}", self.offset),
+            });
+        }
+
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, AccountError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn seek(&mut self, offset: usize) -> Result<(), AccountError> {
+        if offset > self.data.len() {
+            return Err(AccountError::InvalidData {
+                address: String::from(self.address),
+                reason: format!("offset {} is past the end of data", offset),
+            });
+        }
+
+        self.offset = offset;
+
+        Ok(())
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, AccountError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, AccountError> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, AccountError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, AccountError> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn read_pubkey(&mut self) -> Result<String, AccountError> {
+        Ok(bs58::encode(self.read_bytes(32)?).into_string())
+    }
+
+    /// Reads a Borsh string. Metaplex pads fixed-size fields with NUL bytes,
+    /// which are trimmed.
+    pub fn read_string(&mut self) -> Result<String, AccountError> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.read_bytes(len)?;
+
+        Ok(String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+
+    /// Reads a `COption<Pubkey>`: a 4-byte tag followed by 32 bytes that are
+    /// always present, zeroed when the tag is 0.
+    pub fn read_coption_pubkey(&mut self) -> Result<Option<String>, AccountError> {
+        let tag = self.read_u32()?;
+        let pubkey = self.read_pubkey()?;
+
+        Ok(if tag == 0 { None } else { Some(pubkey) })
+    }
+
+    /// Reads an `OptionalNonZeroPubkey`, where all zeroes mean `None`.
+    pub fn read_optional_nonzero_pubkey(&mut self) -> Result<Option<String>, AccountError> {
+        let bytes = self.read_bytes(32)?;
+
+        if bytes.iter().all(|byte| *byte == 0) {
+            return Ok(None);
+        }
+
+        Ok(Some(bs58::encode(bytes).into_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "TestMint111111111111111111111111111111111111";
+
+    fn pubkey(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    fn encoded(byte: u8) -> String {
+        bs58::encode(pubkey(byte)).into_string()
+    }
+
+    fn coption_pubkey(key: Option<u8>) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&u32::from(key.is_some()).to_le_bytes());
+        data.extend_from_slice(&pubkey(key.unwrap_or(0)));
+        data
+    }
+
+    /// The 82-byte base layout shared by Token and Token-2022 mints.
+    fn base_mint(mint_authority: Option<u8>, freeze_authority: Option<u8>) -> Vec<u8> {
+        let mut data = coption_pubkey(mint_authority);
+        data.extend_from_slice(&1_000_000_000_000_000u64.to_le_bytes());
+        data.push(6);
+        data.push(1);
+        data.extend(coption_pubkey(freeze_authority));
+        data
+    }
+
+    fn token_2022_mint(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = base_mint(None, None);
+        data.resize(TOKEN_2022_ACCOUNT_TYPE_OFFSET, 0);
+        data.push(TOKEN_2022_MINT_ACCOUNT_TYPE);
+
+        for (extension_type, value) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+
+        data
+    }
+
+    fn transfer_fee_config(older: (u64, u16), newer: (u64, u16)) -> Vec<u8> {
+        let mut value = vec![];
+        value.extend_from_slice(&pubkey(1));
+        value.extend_from_slice(&pubkey(2));
+        value.extend_from_slice(&0u64.to_le_bytes());
+
+        for (epoch, basis_points) in [older, newer] {
+            value.extend_from_slice(&epoch.to_le_bytes());
+            value.extend_from_slice(&u64::MAX.to_le_bytes());
+            value.extend_from_slice(&basis_points.to_le_bytes());
+        }
+
+        value
+    }
+
+    #[test]
+    fn decodes_token_mint() {
+        let mint = decode_mint(ADDRESS, TokenProgram::Token, &base_mint(Some(3), None), 0).unwrap();
+
+        assert_eq!(mint.mint_authority, Some(encoded(3)));
+        assert_eq!(mint.freeze_authority, None);
+        assert_eq!(mint.supply, 1_000_000_000_000_000);
+        assert_eq!(mint.decimals, 6);
+        assert_eq!(mint.extensions.transfer_fee_basis_points, None);
+    }
+
+    #[test]
+    fn token_2022_mint_without_extensions_uses_base_layout() {
+        let mint = decode_mint(
+            ADDRESS,
+            TokenProgram::Token2022,
+            &base_mint(None, Some(4)),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(mint.freeze_authority, Some(encoded(4)));
+        assert!(mint.extensions.permanent_delegate.is_none());
+    }
+
+    #[test]
+    fn transfer_fee_follows_the_epoch() {
+        let data = token_2022_mint(&[(
+            EXTENSION_TRANSFER_FEE_CONFIG,
+            transfer_fee_config((0, 100), (600, 250)),
+        )]);
+
+        let before = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 599).unwrap();
+        let after = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 600).unwrap();
+
+        assert_eq!(before.extensions.transfer_fee_basis_points, Some(100));
+        assert_eq!(after.extensions.transfer_fee_basis_points, Some(250));
+    }
+
+    #[test]
+    fn decodes_restrictive_extensions() {
+        let mut transfer_hook = pubkey(0).to_vec();
+        transfer_hook.extend_from_slice(&pubkey(9));
+
+        let data = token_2022_mint(&[
+            (EXTENSION_DEFAULT_ACCOUNT_STATE, vec![ACCOUNT_STATE_FROZEN]),
+            (EXTENSION_NON_TRANSFERABLE, vec![]),
+            (EXTENSION_PERMANENT_DELEGATE, pubkey(8).to_vec()),
+            (EXTENSION_TRANSFER_HOOK, transfer_hook),
+            // Unknown extensions are skipped by their length.
+            (99, vec![0xff; 5]),
+        ]);
+
+        let extensions = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 0)
+            .unwrap()
+            .extensions;
+
+        assert!(extensions.default_account_state_frozen);
+        assert!(extensions.non_transferable);
+        assert_eq!(extensions.permanent_delegate, Some(encoded(8)));
+        assert_eq!(extensions.transfer_hook_program, Some(encoded(9)));
+    }
+
+    #[test]
+    fn zeroed_permanent_delegate_is_none() {
+        let data = token_2022_mint(&[(EXTENSION_PERMANENT_DELEGATE, pubkey(0).to_vec())]);
+
+        let extensions = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 0)
+            .unwrap()
+            .extensions;
+
+        assert_eq!(extensions.permanent_delegate, None);
+    }
+
+    #[test]
+    fn rejects_truncated_mint() {
+        let data = base_mint(Some(3), None);
+
+        let result = decode_mint(
+            ADDRESS,
+            TokenProgram::Token,
+            &data[..MINT_ACCOUNT_LEN - 1],
+            0,
+        );
+
+        assert!(matches!(result, Err(AccountError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn rejects_extension_longer_than_account() {
+        let mut data = token_2022_mint(&[(EXTENSION_PERMANENT_DELEGATE, pubkey(8).to_vec())]);
+        data.truncate(data.len() - 1);
+
+        let result = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 0);
+
+        assert!(matches!(result, Err(AccountError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn rejects_truncated_transfer_fee_config() {
+        let mut value = transfer_fee_config((0, 100), (600, 250));
+        value.truncate(value.len() - 2);
+        let data = token_2022_mint(&[(EXTENSION_TRANSFER_FEE_CONFIG, value)]);
+
+        let result = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 0);
+
+        assert!(matches!(result, Err(AccountError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn rejects_non_mint_account_type() {
+        let mut data = token_2022_mint(&[]);
+        data[TOKEN_2022_ACCOUNT_TYPE_OFFSET] = 2;
+
+        let result = decode_mint(ADDRESS, TokenProgram::Token2022, &data, 0);
+
+        assert!(matches!(result, Err(AccountError::InvalidData { .. })));
+    }
+
+    /// A Borsh string padded with NUL bytes the way Metaplex stores it.
+    fn padded_string(value: &str, len: usize) -> Vec<u8> {
+        let mut data = (len as u32).to_le_bytes().to_vec();
+        data.extend_from_slice(value.as_bytes());
+        data.resize(4 + len, 0);
+        data
+    }
+
+    fn metaplex_metadata(creators: &[u8], is_mutable: bool) -> Vec<u8> {
+        let mut data = vec![METAPLEX_METADATA_V1_KEY];
+        data.extend_from_slice(&pubkey(5));
+        data.extend_from_slice(&pubkey(6));
+        data.extend(padded_string("Bonk", 32));
+        data.extend(padded_string("BONK", 10));
+        data.extend(padded_string("https://arweave.net/bonk.json", 200));
+        data.extend_from_slice(&500u16.to_le_bytes());
+
+        data.push(u8::from(!creators.is_empty()));
+        if !creators.is_empty() {
+            data.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+            for creator in creators {
+                data.extend_from_slice(&pubkey(*creator));
+                data.push(1);
+                data.push(100 / creators.len() as u8);
+            }
+        }
+
+        data.push(1);
+        data.push(u8::from(is_mutable));
+        // Collection, uses and the rest are not decoded.
+        data.extend_from_slice(&[0; 16]);
+        data
+    }
+
+    #[test]
+    fn decodes_metaplex_metadata() {
+        let metadata =
+            decode_metaplex_metadata(ADDRESS, &metaplex_metadata(&[7, 8], true)).unwrap();
+
+        assert_eq!(metadata.update_authority, encoded(5));
+        assert_eq!(metadata.mint, encoded(6));
+        assert_eq!(metadata.name, "Bonk");
+        assert_eq!(metadata.symbol, "BONK");
+        assert!(metadata.is_mutable);
+    }
+
+    #[test]
+    fn decodes_metaplex_metadata_without_creators() {
+        let metadata = decode_metaplex_metadata(ADDRESS, &metaplex_metadata(&[], false)).unwrap();
+
+        assert_eq!(metadata.name, "Bonk");
+        assert!(!metadata.is_mutable);
+    }
+
+    #[test]
+    fn rejects_unexpected_metadata_key() {
+        let mut data = metaplex_metadata(&[], false);
+        data[0] = 0;
+
+        let result = decode_metaplex_metadata(ADDRESS, &data);
+
+        assert!(matches!(result, Err(AccountError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn rejects_truncated_metaplex_metadata() {
+        let data = metaplex_metadata(&[7, 8], true);
+        // Cut inside the name, inside the second creator and right before
+        // `is_mutable`.
+        let creators_end = data.len() - 16 - 2;
+
+        for len in [1 + 64 + 10, creators_end - 20, creators_end + 1] {
+            let result = decode_metaplex_metadata(ADDRESS, &data[..len]);
+
+            assert!(
+                matches!(result, Err(AccountError::InvalidData { .. })),
+                "decoded {} bytes",
+                len
+            );
+        }
+    }
+
+    fn bonding_curve(real_token_reserves: u64, real_sol_reserves: u64, complete: bool) -> Vec<u8> {
+        let sold = PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES - real_token_reserves;
+        let virtual_token_reserves = 1_073_000_000_000_000 - sold;
+        let virtual_sol_reserves = 30_000_000_000 + real_sol_reserves;
+
+        let mut data = vec![23, 183, 248, 55, 96, 216, 172, 96];
+        for value in [
+            virtual_token_reserves,
+            virtual_sol_reserves,
+            real_token_reserves,
+            real_sol_reserves,
+            1_000_000_000_000_000,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.push(u8::from(complete));
+        // Newer curves append the creator.
+        data.extend_from_slice(&pubkey(3));
+        data
+    }
+
+    #[test]
+    fn decodes_fresh_bonding_curve() {
+        let data = bonding_curve(PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES, 0, false);
+
+        let curve = decode_bonding_curve(ADDRESS, &data).unwrap();
+
+        assert_eq!(curve.virtual_token_reserves, 1_073_000_000_000_000);
+        assert_eq!(curve.virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(curve.token_total_supply, 1_000_000_000_000_000);
+        assert!(!curve.complete);
+        assert_eq!(curve.progress_percent(), 0.0);
+        assert!((curve.price_in_sol() - 30.0 / 1_073_000_000.0).abs() < 1e-15);
+        assert!((curve.market_cap_in_sol() - 27.958).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bonding_curve_progress_tracks_sold_tokens() {
+        let half = PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES / 2;
+        let curve =
+            decode_bonding_curve(ADDRESS, &bonding_curve(half, 42_000_000_000, false)).unwrap();
+
+        assert!((curve.progress_percent() - 50.0).abs() < 1e-9);
+        assert_eq!(curve.real_sol_reserves_in_sol(), 42.0);
+    }
+
+    #[test]
+    fn completed_bonding_curve_is_full() {
+        let curve = decode_bonding_curve(ADDRESS, &bonding_curve(0, 85_000_000_000, true)).unwrap();
+
+        assert!(curve.complete);
+        assert_eq!(curve.progress_percent(), 100.0);
+    }
+
+    #[test]
+    fn rejects_truncated_bonding_curve() {
+        let data = bonding_curve(PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES, 0, false);
+
+        // Cut inside the discriminator, inside a reserve and before `complete`.
+        for len in [4, 8 + 12, 8 + 5 * 8] {
+            let result = decode_bonding_curve(ADDRESS, &data[..len]);
+
+            assert!(
+                matches!(result, Err(AccountError::InvalidData { .. })),
+                "decoded {} bytes",
+                len
+            );
+        }
+    }
+
+    /// A pool account of `len` bytes with the LP mint and amount at the given
+    /// offsets and every other field zeroed.
+    fn lp_pool(len: usize, lp_mint_offset: usize, lp_issued_offset: usize) -> Vec<u8> {
+        let mut data = vec![0; len];
+        data[lp_mint_offset..lp_mint_offset + 32].copy_from_slice(&pubkey(4));
+        data[lp_issued_offset..lp_issued_offset + 8]
+            .copy_from_slice(&1_234_567_890u64.to_le_bytes());
+        data
+    }
+
+    fn raydium_amm_v4_pool() -> Vec<u8> {
+        // `LiquidityStateV4` is 752 bytes.
+        lp_pool(
+            752,
+            RAYDIUM_AMM_V4_LP_MINT_OFFSET,
+            RAYDIUM_AMM_V4_LP_RESERVE_OFFSET,
+        )
+    }
+
+    fn raydium_cpmm_pool() -> Vec<u8> {
+        // Discriminator plus the 629-byte `PoolState`.
+        lp_pool(
+            637,
+            RAYDIUM_CPMM_LP_MINT_OFFSET,
+            RAYDIUM_CPMM_LP_SUPPLY_OFFSET,
+        )
+    }
+
+    #[test]
+    fn decodes_raydium_amm_v4_lp() {
+        let lp_pool = decode_raydium_amm_v4_lp(ADDRESS, &raydium_amm_v4_pool()).unwrap();
+
+        assert_eq!(lp_pool.lp_mint, encoded(4));
+        assert_eq!(lp_pool.lp_issued, 1_234_567_890);
+    }
+
+    #[test]
+    fn decodes_raydium_cpmm_lp() {
+        let lp_pool = decode_raydium_cpmm_lp(ADDRESS, &raydium_cpmm_pool()).unwrap();
+
+        assert_eq!(lp_pool.lp_mint, encoded(4));
+        assert_eq!(lp_pool.lp_issued, 1_234_567_890);
+    }
+
+    #[test]
+    fn rejects_truncated_raydium_pools() {
+        let amm_v4 = raydium_amm_v4_pool();
+        let cpmm = raydium_cpmm_pool();
+
+        // Cut inside the LP mint and before the LP amount.
+        for len in [
+            RAYDIUM_AMM_V4_LP_MINT_OFFSET + 16,
+            RAYDIUM_AMM_V4_LP_RESERVE_OFFSET + 4,
+        ] {
+            let result = decode_raydium_amm_v4_lp(ADDRESS, &amm_v4[..len]);
+
+            assert!(
+                matches!(result, Err(AccountError::InvalidData { .. })),
+                "decoded {} bytes",
+                len
+            );
+        }
+
+        for len in [
+            RAYDIUM_CPMM_LP_MINT_OFFSET + 16,
+            RAYDIUM_CPMM_LP_SUPPLY_OFFSET - 1,
+        ] {
+            let result = decode_raydium_cpmm_lp(ADDRESS, &cpmm[..len]);
+
+            assert!(
+                matches!(result, Err(AccountError::InvalidData { .. })),
+                "decoded {} bytes",
+                len
+            );
+        }
+    }
+}
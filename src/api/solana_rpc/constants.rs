@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+pub const SOLANA_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+pub const DEFAULT_COMMITMENT: &str = "confirmed";
+/// Upper bound the RPC node accepts for `getMultipleAccounts`.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+/// Upper bound the RPC node accepts for `getSignaturesForAddress`.
+pub const MAX_SIGNATURES_PER_PAGE: usize = 1000;
+/// Launch transactions inspected at most when looking for snipers.
+pub const MAX_LAUNCH_TRANSACTIONS: usize = 60;
+pub const CONCURRENT_TRANSACTION_REQUESTS: usize = 10;
+/// Slack between Jupiter's pool creation time and the on-chain block time.
+pub const LAUNCH_TIME_TOLERANCE_SECS: i64 = 60;
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const METAPLEX_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Mainnet epochs have a fixed length since warmup ended.
+pub const SLOTS_PER_EPOCH: u64 = 432_000;
+
+pub const MINT_ACCOUNT_LEN: usize = 82;
+/// Token-2022 pads mints to the token account size before the account type
+/// byte, so both account kinds share the same TLV offset.
+pub const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = 165;
+pub const TOKEN_2022_MINT_ACCOUNT_TYPE: u8 = 1;
+
+pub const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+pub const EXTENSION_DEFAULT_ACCOUNT_STATE: u16 = 6;
+pub const EXTENSION_NON_TRANSFERABLE: u16 = 9;
+pub const EXTENSION_PERMANENT_DELEGATE: u16 = 12;
+pub const EXTENSION_TRANSFER_HOOK: u16 = 14;
+pub const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+pub const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+pub const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+pub const PUMP_FUN_BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+pub const PUMP_FUN_TOKEN_DECIMALS: i32 = 6;
+/// Real token reserves a fresh pump.fun curve starts with (793.1M tokens).
+pub const PUMP_FUN_INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+pub const INCINERATOR_ADDRESS: &str = "1nc1nerator11111111111111111111111111111111";
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+pub const RAYDIUM_AMM_V4_AUTHORITY: &str = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+pub const RAYDIUM_CPMM_PROGRAM_ID: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+pub const RAYDIUM_CPMM_AUTHORITY: &str = "GpMZbSM2GgvTKHJirzeGfMFoaZ8UR2X7F4v8vHTvxFbL";
+pub const RAYDIUM_CLMM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+pub const PUMP_SWAP_PROGRAM_ID: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+pub const METEORA_DLMM_PROGRAM_ID: &str = "LBUZKhRxPF3XUpBCjp4YzTKLLccjUhTSktoXmw3nbm9";
+
+/// Holders that are not real wallets: burn addresses and pool authorities.
+pub const EXCLUDED_HOLDER_OWNERS: [&str; 3] = [
+    INCINERATOR_ADDRESS,
+    RAYDIUM_AMM_V4_AUTHORITY,
+    RAYDIUM_CPMM_AUTHORITY,
+];
+
+/// Programs whose PDAs hold pool vaults, including the pump.fun curve.
+pub const POOL_PROGRAM_IDS: [&str; 7] = [
+    PUMP_FUN_PROGRAM_ID,
+    PUMP_SWAP_PROGRAM_ID,
+    RAYDIUM_AMM_V4_PROGRAM_ID,
+    RAYDIUM_CPMM_PROGRAM_ID,
+    RAYDIUM_CLMM_PROGRAM_ID,
+    ORCA_WHIRLPOOL_PROGRAM_ID,
+    METEORA_DLMM_PROGRAM_ID,
+];
+
+pub const STREAMFLOW_PROGRAM_ID: &str = "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m";
+pub const RAYDIUM_LP_LOCK_PROGRAM_ID: &str = "LockrWmn6K5twhz3y9w1dQERbmgSaRkfnTeTKbpofwE";
+
+/// Programs known to escrow LP tokens until an unlock date.
+pub const LOCK_PROGRAM_IDS: [&str; 2] = [STREAMFLOW_PROGRAM_ID, RAYDIUM_LP_LOCK_PROGRAM_ID];
+
+/// Offsets into `LiquidityStateV4`, which has no discriminator.
+pub const RAYDIUM_AMM_V4_LP_MINT_OFFSET: usize = 464;
+pub const RAYDIUM_AMM_V4_LP_RESERVE_OFFSET: usize = 720;
+/// Offsets into the CPMM `PoolState`, after the 8-byte Anchor discriminator.
+pub const RAYDIUM_CPMM_LP_MINT_OFFSET: usize = 136;
+pub const RAYDIUM_CPMM_LP_SUPPLY_OFFSET: usize = 333;
+
+pub const METAPLEX_METADATA_SEED: &[u8] = b"metadata";
+pub const METAPLEX_METADATA_V1_KEY: u8 = 4;
+pub const METAPLEX_METADATA_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
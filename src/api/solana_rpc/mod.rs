@@ -0,0 +1,5 @@
+pub mod accounts;
+pub mod constants;
+pub mod models;
+pub mod pda;
+pub mod solana_rpc_client;
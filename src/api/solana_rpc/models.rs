@@ -0,0 +1,117 @@
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// `result` is kept raw because some methods legitimately return `null`, so
+/// a `null` result is `Some(Value::Null)` and only a missing one is `None`.
+#[derive(Debug, Deserialize)]
+pub struct RpcResponse {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub result: Option<Value>,
+    pub error: Option<RpcError>,
+}
+
+fn deserialize_present<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcContextValue<T> {
+    pub context: RpcContext,
+    pub value: T,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcContext {
+    pub slot: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcAccountInfo {
+    pub owner: String,
+    /// `[data, encoding]` pair, always requested as base64.
+    pub data: (String, String),
+}
+
+/// Account with its data already decoded from base64.
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub address: String,
+    pub owner: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAmount {
+    pub amount: String,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccountBalance {
+    pub address: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    #[serde(rename = "blockTime")]
+    pub block_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedTransaction {
+    pub meta: Option<TransactionMeta>,
+    pub transaction: TransactionBody,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionMeta {
+    pub err: Option<Value>,
+    #[serde(rename = "preTokenBalances", default)]
+    pub pre_token_balances: Vec<TransactionTokenBalance>,
+    #[serde(rename = "postTokenBalances", default)]
+    pub post_token_balances: Vec<TransactionTokenBalance>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionTokenBalance {
+    #[serde(rename = "accountIndex")]
+    pub account_index: usize,
+    pub mint: String,
+    pub owner: Option<String>,
+    #[serde(rename = "uiTokenAmount")]
+    pub ui_token_amount: UiTokenAmount,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UiTokenAmount {
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionBody {
+    pub message: TransactionMessage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionMessage {
+    #[serde(rename = "accountKeys")]
+    pub account_keys: Vec<TransactionAccountKey>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransactionAccountKey {
+    pub pubkey: String,
+}
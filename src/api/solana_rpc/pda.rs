@@ -0,0 +1,49 @@
+use crate::errors::AccountError;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Finds the canonical program derived address and bump for the given seeds,
+/// the same way `Pubkey::find_program_address` does.
+pub fn find_program_address(
+    seeds: &[&[u8]],
+    program_id: &str,
+) -> Result<(String, u8), AccountError> {
+    let program_id_bytes = decode_pubkey(program_id)?;
+
+    for bump in (0..=u8::MAX).rev() {
+        let mut hasher = Sha256::new();
+
+        for seed in seeds {
+            hasher.update(seed);
+        }
+
+        hasher.update([bump]);
+        hasher.update(program_id_bytes);
+        hasher.update(PDA_MARKER);
+
+        let hash: [u8; 32] = hasher.finalize().into();
+
+        // A PDA must not be a valid ed25519 public key.
+        if CompressedEdwardsY(hash).decompress().is_none() {
+            return Ok((bs58::encode(hash).into_string(), bump));
+        }
+    }
+
+    Err(AccountError::InvalidData {
+        address: String::from(program_id),
+        reason: String::from("no viable bump seed found"),
+    })
+}
+
+pub fn decode_pubkey(address: &str) -> Result<[u8; 32], AccountError> {
+    bs58::decode(address)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AccountError::InvalidData {
+            address: String::from(address),
+            reason: String::from("not a base58 encoded public key"),
+        })
+}
@@ -0,0 +1,822 @@
+use super::accounts::{
+    BondingCurve, MetaplexMetadata, MintAccount, TokenAccount, TokenProgram, decode_bonding_curve,
+    decode_metaplex_metadata, decode_mint, decode_raydium_amm_v4_lp, decode_raydium_cpmm_lp,
+    decode_token_account,
+};
+use super::constants::{
+    CONCURRENT_TRANSACTION_REQUESTS, DEFAULT_COMMITMENT, EXCLUDED_HOLDER_OWNERS,
+    INCINERATOR_ADDRESS, LAUNCH_TIME_TOLERANCE_SECS, LOCK_PROGRAM_IDS, MAX_LAUNCH_TRANSACTIONS,
+    MAX_MULTIPLE_ACCOUNTS, MAX_SIGNATURES_PER_PAGE, METAPLEX_METADATA_CACHE_TTL,
+    METAPLEX_METADATA_PROGRAM_ID, METAPLEX_METADATA_SEED, POOL_PROGRAM_IDS,
+    PUMP_FUN_BONDING_CURVE_SEED, PUMP_FUN_PROGRAM_ID, RAYDIUM_AMM_V4_PROGRAM_ID,
+    RAYDIUM_CPMM_PROGRAM_ID, SLOTS_PER_EPOCH, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use super::models::{
+    Account, ParsedTransaction, RpcAccountInfo, RpcContextValue, RpcResponse, SignatureInfo,
+    TokenAccountBalance, TokenAmount,
+};
+use super::pda::{decode_pubkey, find_program_address};
+use crate::api::cache::ResponseCache;
+use crate::api::circuit_breaker::CircuitBreaker;
+use crate::api::errors::{ApiError, ApiErrorData, non_json_error_body};
+use crate::api::fixtures::FixtureStore;
+use crate::api::models::{
+    HolderConcentration, LiquidityLock, SniperReport, TokenHolderInfo, TokenLinks,
+    TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo,
+};
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::retry::RetryPolicy;
+use crate::api::traits::TokenDataProvider;
+use crate::errors::AppError;
+use crate::errors::{AccountError, ProviderError};
+use crate::utils::single_flight::{SharedError, SingleFlight};
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::{Client, Method, Response, header::CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, from_str, from_value, json};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::debug;
+
+pub struct SolanaRpcClient {
+    client: Client,
+    endpoint: String,
+    cache: ResponseCache,
+    in_flight: SingleFlight<Result<Value, SharedError>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    fixtures: Arc<FixtureStore>,
+}
+
+impl SolanaRpcClient {
+    pub fn new(
+        endpoint: &str,
+        retry_policy: RetryPolicy,
+        rate_limiter: Arc<RateLimiter>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        fixtures: Arc<FixtureStore>,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            client: Client::builder()
+                .timeout(retry_policy.attempt_timeout)
+                .build()?,
+            endpoint: String::from(endpoint),
+            cache: ResponseCache::new(),
+            in_flight: SingleFlight::new(),
+            retry_policy,
+            rate_limiter,
+            circuit_breaker,
+            fixtures,
+        })
+    }
+
+    /// Returns `None` when the account does not exist.
+    pub async fn get_account_info(&self, address: &str) -> Result<Option<Account>, AppError> {
+        Ok(self.get_account_info_at_slot(address).await?.0)
+    }
+
+    /// `get_account_info` for accounts that rarely change, served from the
+    /// cache for `ttl`.
+    async fn get_cached_account_info(
+        &self,
+        address: &str,
+        ttl: Duration,
+    ) -> Result<Option<Account>, AppError> {
+        let account_info: RpcContextValue<Option<RpcAccountInfo>> = self
+            .call_cached(
+                "getAccountInfo",
+                json!([address, { "encoding": "base64", "commitment": DEFAULT_COMMITMENT }]),
+                ttl,
+            )
+            .await?;
+
+        account_info
+            .value
+            .map(|account| Self::decode_account(address, account))
+            .transpose()
+    }
+
+    /// `get_account_info` along with the slot the account was read at.
+    async fn get_account_info_at_slot(
+        &self,
+        address: &str,
+    ) -> Result<(Option<Account>, u64), AppError> {
+        let account_info: RpcContextValue<Option<RpcAccountInfo>> = self
+            .call(
+                "getAccountInfo",
+                json!([address, { "encoding": "base64", "commitment": DEFAULT_COMMITMENT }]),
+            )
+            .await?;
+
+        let account = account_info
+            .value
+            .map(|account| Self::decode_account(address, account))
+            .transpose()?;
+
+        Ok((account, account_info.context.slot))
+    }
+
+    /// Fetches and decodes the mint account of an SPL Token or Token-2022 mint.
+    pub async fn get_mint(&self, mint: &str) -> Result<MintAccount, AppError> {
+        let (account, slot) = self.get_account_info_at_slot(mint).await?;
+        let account = account.ok_or_else(|| AccountError::NotFound {
+            address: String::from(mint),
+        })?;
+
+        let token_program = match account.owner.as_str() {
+            TOKEN_PROGRAM_ID => TokenProgram::Token,
+            TOKEN_2022_PROGRAM_ID => TokenProgram::Token2022,
+            _ => {
+                return Err(AccountError::UnexpectedOwner {
+                    address: account.address,
+                    owner: account.owner,
+                }
+                .into());
+            }
+        };
+
+        Ok(decode_mint(
+            mint,
+            token_program,
+            &account.data,
+            slot / SLOTS_PER_EPOCH,
+        )?)
+    }
+
+    /// Fetches and decodes the Metaplex metadata account derived from the mint.
+    /// Both the scan and the metadata fallback ask for it, so it is cached.
+    pub async fn get_metaplex_metadata(&self, mint: &str) -> Result<MetaplexMetadata, AppError> {
+        let metadata_program_id = decode_pubkey(METAPLEX_METADATA_PROGRAM_ID)?;
+        let mint_bytes = decode_pubkey(mint)?;
+        let (metadata_address, _bump) = find_program_address(
+            &[METAPLEX_METADATA_SEED, &metadata_program_id, &mint_bytes],
+            METAPLEX_METADATA_PROGRAM_ID,
+        )?;
+
+        let account = self
+            .get_cached_account_info(&metadata_address, METAPLEX_METADATA_CACHE_TTL)
+            .await?
+            .ok_or_else(|| AccountError::NotFound {
+                address: metadata_address.clone(),
+            })?;
+
+        if account.owner != METAPLEX_METADATA_PROGRAM_ID {
+            return Err(AccountError::UnexpectedOwner {
+                address: account.address,
+                owner: account.owner,
+            }
+            .into());
+        }
+
+        Ok(decode_metaplex_metadata(&metadata_address, &account.data)?)
+    }
+
+    /// Returns `None` when the mint was not launched through pump.fun.
+    pub async fn get_pump_fun_bonding_curve(
+        &self,
+        mint: &str,
+    ) -> Result<Option<BondingCurve>, AppError> {
+        let mint_bytes = decode_pubkey(mint)?;
+        let (bonding_curve_address, _bump) = find_program_address(
+            &[PUMP_FUN_BONDING_CURVE_SEED, &mint_bytes],
+            PUMP_FUN_PROGRAM_ID,
+        )?;
+
+        match self.get_account_info(&bonding_curve_address).await? {
+            Some(account) if account.owner == PUMP_FUN_PROGRAM_ID => Ok(Some(
+                decode_bonding_curve(&bonding_curve_address, &account.data)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Computes how much of the supply the largest holders own. Token accounts
+    /// are resolved to their owners so that pool vaults, the pump.fun curve and
+    /// burn addresses can be left out.
+    pub async fn get_holder_concentration(
+        &self,
+        mint: &str,
+    ) -> Result<HolderConcentration, AppError> {
+        let supply = self.get_token_supply(mint).await?.amount.parse::<u64>()?;
+        let token_accounts = self.get_largest_token_accounts_with_owners(mint).await?;
+
+        // Largest accounts come sorted by amount, so the order is preserved.
+        let holder_amounts: Vec<u64> = token_accounts
+            .into_iter()
+            .filter(|(token_account, owner_program)| {
+                !EXCLUDED_HOLDER_OWNERS.contains(&token_account.owner.as_str())
+                    && !owner_program
+                        .as_deref()
+                        .is_some_and(|program| POOL_PROGRAM_IDS.contains(&program))
+            })
+            .map(|(token_account, _)| token_account.amount)
+            .collect();
+
+        let percent_of_top = |count: usize| {
+            if supply == 0 {
+                return 0.0;
+            }
+
+            let held: u64 = holder_amounts.iter().take(count).sum();
+            held as f64 * 100.0 / supply as f64
+        };
+
+        Ok(HolderConcentration {
+            top_1_percent: percent_of_top(1),
+            top_10_percent: percent_of_top(10),
+            top_n_percent: percent_of_top(holder_amounts.len()),
+            top_n: holder_amounts.len(),
+        })
+    }
+
+    /// Reads how much of the LP supply of a pool is burned or sits in a lock
+    /// program. Returns `None` for pools without an LP mint.
+    pub async fn get_liquidity_lock(
+        &self,
+        pair_address: &str,
+    ) -> Result<Option<LiquidityLock>, AppError> {
+        let pool =
+            self.get_account_info(pair_address)
+                .await?
+                .ok_or_else(|| AccountError::NotFound {
+                    address: String::from(pair_address),
+                })?;
+
+        let lp_pool = match pool.owner.as_str() {
+            RAYDIUM_AMM_V4_PROGRAM_ID => decode_raydium_amm_v4_lp(pair_address, &pool.data)?,
+            RAYDIUM_CPMM_PROGRAM_ID => decode_raydium_cpmm_lp(pair_address, &pool.data)?,
+            _ => return Ok(None),
+        };
+
+        if lp_pool.lp_issued == 0 {
+            return Ok(None);
+        }
+
+        // LP burned through the token program shrinks the supply without the
+        // pool noticing, the rest is found by looking at who holds it.
+        let lp_supply = self
+            .get_token_supply(&lp_pool.lp_mint)
+            .await?
+            .amount
+            .parse::<u64>()?;
+        let mut burned = lp_pool.lp_issued.saturating_sub(lp_supply);
+        let mut locked = 0;
+
+        for (token_account, owner_program) in self
+            .get_largest_token_accounts_with_owners(&lp_pool.lp_mint)
+            .await?
+        {
+            if token_account.owner == INCINERATOR_ADDRESS {
+                burned += token_account.amount;
+            } else if LOCK_PROGRAM_IDS.contains(&token_account.owner.as_str())
+                || owner_program
+                    .as_deref()
+                    .is_some_and(|program| LOCK_PROGRAM_IDS.contains(&program))
+            {
+                locked += token_account.amount;
+            }
+        }
+
+        let percent_of_issued =
+            |amount: u64| (amount as f64 * 100.0 / lp_pool.lp_issued as f64).min(100.0);
+
+        Ok(Some(LiquidityLock {
+            burned_percent: percent_of_issued(burned),
+            locked_percent: percent_of_issued(locked),
+        }))
+    }
+
+    /// Finds wallets that bought the token within `slot_window` slots of its
+    /// launch and how much of the supply they still hold. The launch is located
+    /// by paging back through the history of the mint and launch pool, so
+    /// tokens with more than `max_pages` pages of history are skipped.
+    pub async fn get_launch_snipers(
+        &self,
+        mint: &str,
+        launch_pool: Option<&str>,
+        launch_time: Option<DateTime<Utc>>,
+        slot_window: u64,
+        max_pages: usize,
+    ) -> Result<Option<SniperReport>, AppError> {
+        // Signatures with their position inside their slot.
+        let mut launch_signatures: Vec<(SignatureInfo, usize)> = vec![];
+
+        for address in std::iter::once(mint).chain(launch_pool) {
+            if let Some(signatures) = self
+                .get_signatures_since_launch(address, launch_time, max_pages)
+                .await?
+            {
+                // Newest first, so reversed they follow block order.
+                let mut previous_slot = None;
+                let mut position = 0;
+
+                for signature in signatures.into_iter().rev() {
+                    if previous_slot != Some(signature.slot) {
+                        previous_slot = Some(signature.slot);
+                        position = 0;
+                    }
+
+                    launch_signatures.push((signature, position));
+                    position += 1;
+                }
+            }
+        }
+
+        let Some(launch_slot) = launch_signatures
+            .iter()
+            .map(|(signature, _)| signature.slot)
+            .min()
+        else {
+            return Ok(None);
+        };
+
+        // The earliest buys are kept when the launch had more transactions
+        // than are inspected. The mint and the pool share some transactions.
+        let mut candidates: Vec<(u64, usize, String)> = launch_signatures
+            .into_iter()
+            .filter(|(signature, _)| {
+                signature.err.is_none() && signature.slot <= launch_slot + slot_window
+            })
+            .map(|(signature, position)| (signature.slot, position, signature.signature))
+            .collect();
+        candidates.sort();
+
+        let mut seen: HashSet<String> = HashSet::new();
+        let candidate_signatures: Vec<String> = candidates
+            .into_iter()
+            .map(|(_, _, signature)| signature)
+            .filter(|signature| seen.insert(signature.clone()))
+            .take(MAX_LAUNCH_TRANSACTIONS)
+            .collect();
+
+        // A transaction that fails to load (a timeout, a pruned slot) is
+        // skipped, the report is built from the ones that came back.
+        let mut transactions = vec![];
+        let mut last_error = None;
+        for chunk in candidate_signatures.chunks(CONCURRENT_TRANSACTION_REQUESTS) {
+            for transaction in join_all(
+                chunk
+                    .iter()
+                    .map(|signature| self.get_transaction(signature)),
+            )
+            .await
+            {
+                match transaction {
+                    Ok(transaction) => transactions.extend(transaction),
+                    Err(e) => {
+                        e.log();
+                        last_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        if transactions.is_empty()
+            && let Some(e) = last_error
+        {
+            return Err(e);
+        }
+
+        // Buyer wallet -> token accounts whose balance of the mint went up.
+        let mut buyers: HashMap<String, HashSet<String>> = HashMap::new();
+        for transaction in &transactions {
+            let Some(meta) = transaction.meta.as_ref().filter(|meta| meta.err.is_none()) else {
+                continue;
+            };
+
+            for post_balance in meta.post_token_balances.iter().filter(|b| b.mint == mint) {
+                let Some(owner) = &post_balance.owner else {
+                    continue;
+                };
+
+                let pre_amount = meta
+                    .pre_token_balances
+                    .iter()
+                    .find(|pre_balance| pre_balance.account_index == post_balance.account_index)
+                    .map(|pre_balance| pre_balance.ui_token_amount.amount.parse::<u64>())
+                    .transpose()?
+                    .unwrap_or_default();
+                let post_amount = post_balance.ui_token_amount.amount.parse::<u64>()?;
+
+                if let Some(account_key) = transaction
+                    .transaction
+                    .message
+                    .account_keys
+                    .get(post_balance.account_index)
+                    && post_amount > pre_amount
+                {
+                    buyers
+                        .entry(owner.clone())
+                        .or_default()
+                        .insert(account_key.pubkey.clone());
+                }
+            }
+        }
+
+        // Pool vaults and the curve also "receive" tokens when liquidity is added.
+        let buyer_addresses: Vec<String> = buyers.keys().cloned().collect();
+        for (buyer, account) in buyer_addresses
+            .iter()
+            .zip(self.get_multiple_accounts(&buyer_addresses).await?)
+        {
+            let is_pool =
+                account.is_some_and(|account| POOL_PROGRAM_IDS.contains(&account.owner.as_str()));
+
+            if is_pool
+                || EXCLUDED_HOLDER_OWNERS.contains(&buyer.as_str())
+                || Some(buyer.as_str()) == launch_pool
+            {
+                buyers.remove(buyer);
+            }
+        }
+
+        let buyer_token_accounts: Vec<String> = buyers.values().flatten().cloned().collect();
+        let mut held: u64 = 0;
+        for account in self
+            .get_multiple_accounts(&buyer_token_accounts)
+            .await?
+            .into_iter()
+            .flatten()
+        {
+            let token_account = decode_token_account(&account.address, &account.data)?;
+
+            if token_account.mint == mint {
+                held += token_account.amount;
+            }
+        }
+
+        let supply = self.get_token_supply(mint).await?.amount.parse::<u64>()?;
+
+        Ok(Some(SniperReport {
+            slot_window,
+            wallet_count: buyers.len(),
+            held_percent: if supply == 0 {
+                0.0
+            } else {
+                held as f64 * 100.0 / supply as f64
+            },
+        }))
+    }
+
+    /// Block time of the oldest transaction touching `address`, `None` if the
+    /// history is longer than `max_pages` pages.
+    pub async fn get_first_signature_time(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let signatures = self
+            .get_signatures_since_launch(address, None, max_pages)
+            .await?;
+
+        Ok(signatures
+            .and_then(|signatures| {
+                signatures
+                    .iter()
+                    .rev()
+                    .find_map(|signature| signature.block_time)
+            })
+            .and_then(|block_time| DateTime::from_timestamp(block_time, 0)))
+    }
+
+    /// Pages back through the signatures of an address until the launch time,
+    /// or until the history is exhausted. Returns `None` if neither happens
+    /// within `max_pages`.
+    async fn get_signatures_since_launch(
+        &self,
+        address: &str,
+        launch_time: Option<DateTime<Utc>>,
+        max_pages: usize,
+    ) -> Result<Option<Vec<SignatureInfo>>, AppError> {
+        let cutoff = launch_time.map(|time| time.timestamp() - LAUNCH_TIME_TOLERANCE_SECS);
+        let mut signatures = vec![];
+        let mut before: Option<String> = None;
+
+        for _ in 0..max_pages {
+            let page = self
+                .get_signatures_for_address(address, before.as_deref(), MAX_SIGNATURES_PER_PAGE)
+                .await?;
+            let exhausted = page.len() < MAX_SIGNATURES_PER_PAGE;
+            before = page.last().map(|signature| signature.signature.clone());
+
+            for signature in page {
+                if let (Some(cutoff), Some(block_time)) = (cutoff, signature.block_time)
+                    && block_time < cutoff
+                {
+                    return Ok(Some(signatures));
+                }
+
+                signatures.push(signature);
+            }
+
+            if exhausted {
+                return Ok(Some(signatures));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns `None` when the node does not know the transaction.
+    pub async fn get_transaction(
+        &self,
+        signature: &str,
+    ) -> Result<Option<ParsedTransaction>, AppError> {
+        self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "jsonParsed",
+                "maxSupportedTransactionVersion": 0,
+                "commitment": DEFAULT_COMMITMENT,
+            }]),
+        )
+        .await
+    }
+
+    /// Resolves the largest token accounts of a mint together with the
+    /// program owning each account's owner, `None` if the owner has no account.
+    async fn get_largest_token_accounts_with_owners(
+        &self,
+        mint: &str,
+    ) -> Result<Vec<(TokenAccount, Option<String>)>, AppError> {
+        let token_account_addresses: Vec<String> = self
+            .get_token_largest_accounts(mint)
+            .await?
+            .into_iter()
+            // Emptied accounts hold nothing worth resolving an owner for.
+            .filter(|account| account.amount != "0")
+            .map(|account| account.address)
+            .collect();
+
+        let mut token_accounts = vec![];
+        for account in self
+            .get_multiple_accounts(&token_account_addresses)
+            .await?
+            .into_iter()
+            .flatten()
+        {
+            token_accounts.push(decode_token_account(&account.address, &account.data)?);
+        }
+
+        let owner_addresses: Vec<String> = token_accounts
+            .iter()
+            .map(|token_account| token_account.owner.clone())
+            .collect();
+
+        let owner_programs = self.get_multiple_accounts(&owner_addresses).await?;
+
+        Ok(token_accounts
+            .into_iter()
+            .zip(owner_programs)
+            .map(|(token_account, owner)| (token_account, owner.map(|owner| owner.owner)))
+            .collect())
+    }
+
+    pub async fn get_multiple_accounts(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<Option<Account>>, AppError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let chunk_accounts: RpcContextValue<Vec<Option<RpcAccountInfo>>> = self
+                .call(
+                    "getMultipleAccounts",
+                    json!([chunk, { "encoding": "base64", "commitment": DEFAULT_COMMITMENT }]),
+                )
+                .await?;
+
+            for (address, account) in chunk.iter().zip(chunk_accounts.value) {
+                accounts.push(
+                    account
+                        .map(|account| Self::decode_account(address, account))
+                        .transpose()?,
+                );
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    pub async fn get_token_supply(&self, mint: &str) -> Result<TokenAmount, AppError> {
+        let token_supply: RpcContextValue<TokenAmount> = self
+            .call(
+                "getTokenSupply",
+                json!([mint, { "commitment": DEFAULT_COMMITMENT }]),
+            )
+            .await?;
+
+        debug!(
+            "Solana Rpc | {} supply {} at {} decimals",
+            mint, token_supply.value.amount, token_supply.value.decimals
+        );
+
+        Ok(token_supply.value)
+    }
+
+    /// Returns up to the 20 largest token accounts of the mint.
+    pub async fn get_token_largest_accounts(
+        &self,
+        mint: &str,
+    ) -> Result<Vec<TokenAccountBalance>, AppError> {
+        let largest_accounts: RpcContextValue<Vec<TokenAccountBalance>> = self
+            .call(
+                "getTokenLargestAccounts",
+                json!([mint, { "commitment": DEFAULT_COMMITMENT }]),
+            )
+            .await?;
+
+        Ok(largest_accounts.value)
+    }
+
+    /// Returns signatures newest first, starting before `before` if given.
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &str,
+        before: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>, AppError> {
+        let mut config = json!({ "limit": limit, "commitment": DEFAULT_COMMITMENT });
+
+        if let Some(before) = before {
+            config["before"] = json!(before);
+        }
+
+        self.call("getSignaturesForAddress", json!([address, config]))
+            .await
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, AppError> {
+        self.circuit_breaker
+            .run(self.send_request(method, params))
+            .await
+    }
+
+    /// `call` with the result cached for `ttl`, keyed by method and params.
+    /// Concurrent callers share one request.
+    async fn call_cached<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+        ttl: Duration,
+    ) -> Result<T, AppError> {
+        let key = format!("{} {}", method, params);
+
+        if let Some(result) = self.cache.get(&key) {
+            return Ok(from_str(&result)?);
+        }
+
+        let result = self
+            .in_flight
+            .run(&key, async {
+                let result = self
+                    .call::<Value>(method, params)
+                    .await
+                    .map_err(SharedError::from)?;
+                self.cache.insert(key.clone(), result.to_string(), ttl);
+
+                Ok::<Value, SharedError>(result)
+            })
+            .await?;
+
+        Ok(from_value(result)?)
+    }
+
+    async fn send_request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, AppError> {
+        // Params carry the mint or account, so they key the fixture too.
+        let fixture_key = format!("{} {}", method, params);
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        })
+        .to_string();
+
+        let response = self
+            .fixtures
+            .through("rpc", &fixture_key, async {
+                Ok(self
+                    .retry_policy
+                    .send("Solana Rpc", &self.rate_limiter, || {
+                        self.client
+                            .request(Method::POST, &self.endpoint)
+                            .header(CONTENT_TYPE, "application/json")
+                            .body(body.clone())
+                    })
+                    .await?)
+            })
+            .await?;
+        let response = self.handle_response(response).await?;
+        let response_body = response.text().await?;
+
+        let rpc_response: RpcResponse = from_str(&response_body)?;
+
+        if let Some(rpc_error) = rpc_response.error {
+            let error_data = ApiErrorData {
+                source: String::from("Solana Rpc"),
+                status_code: 200,
+                message: format!("{} ({}): {}", method, rpc_error.code, rpc_error.message),
+            };
+
+            return match rpc_error.code {
+                -32600 | -32602 => Err(ApiError::BadRequest { error_data }.into()),
+                -32005 => Err(ApiError::ServiceUnavailable { error_data }.into()),
+                _ => Err(ApiError::Unknown { error_data }.into()),
+            };
+        }
+
+        let result = rpc_response.result.ok_or_else(|| -> AppError {
+            ApiError::Unknown {
+                error_data: ApiErrorData {
+                    source: String::from("Solana Rpc"),
+                    status_code: 200,
+                    message: format!("{}: response has neither result nor error", method),
+                },
+            }
+            .into()
+        })?;
+
+        Ok(from_value(result)?)
+    }
+
+    fn unsupported(&self, field: &str) -> AppError {
+        ProviderError::Unsupported {
+            provider: String::from(self.name()),
+            field: String::from(field),
+        }
+        .into()
+    }
+
+    fn decode_account(address: &str, account: RpcAccountInfo) -> Result<Account, AppError> {
+        Ok(Account {
+            address: String::from(address),
+            owner: account.owner,
+            data: STANDARD.decode(account.data.0)?,
+        })
+    }
+
+    async fn handle_response(&self, response: Response) -> Result<Response, AppError> {
+        let status_code = response.status().as_u16();
+
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let error_data = ApiErrorData {
+            source: String::from("Solana Rpc"),
+            status_code,
+            message: non_json_error_body(&response.text().await?),
+        };
+
+        Err(ApiError::from_status(error_data).into())
+    }
+}
+
+/// On-chain fallback for tokens the indexers have not picked up yet. Only
+/// metadata is available, prices and holders need an indexer.
+#[async_trait]
+impl TokenDataProvider for SolanaRpcClient {
+    fn name(&self) -> &'static str {
+        "rpc"
+    }
+
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError> {
+        let metadata = self.get_metaplex_metadata(token_address).await?;
+
+        Ok(TokenMetadataInfo {
+            mint: metadata.mint,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            logo: String::new(),
+            fully_diluted_value: None,
+            links: TokenLinks::default(),
+        })
+    }
+
+    async fn fetch_primary_pair(&self, _token_address: &str) -> Result<TokenPairInfo, AppError> {
+        Err(self.unsupported("primary pair"))
+    }
+
+    async fn fetch_pair_stats(
+        &self,
+        _token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError> {
+        Err(self.unsupported("pair stats"))
+    }
+
+    async fn fetch_holders(&self, _token_address: &str) -> Result<TokenHolderInfo, AppError> {
+        Err(self.unsupported("holders"))
+    }
+}
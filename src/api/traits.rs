@@ -0,0 +1,16 @@
+use super::models::{TokenHolderInfo, TokenMetadataInfo, TokenPairInfo, TokenPairStatsInfo};
+use crate::errors::AppError;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait TokenDataProvider: Send + Sync {
+    /// Name used to reference the provider in the `*_SOURCES` config lists.
+    fn name(&self) -> &'static str;
+    async fn fetch_metadata(&self, token_address: &str) -> Result<TokenMetadataInfo, AppError>;
+    async fn fetch_primary_pair(&self, token_address: &str) -> Result<TokenPairInfo, AppError>;
+    async fn fetch_pair_stats(
+        &self,
+        token_pair: &TokenPairInfo,
+    ) -> Result<TokenPairStatsInfo, AppError>;
+    async fn fetch_holders(&self, token_address: &str) -> Result<TokenHolderInfo, AppError>;
+}
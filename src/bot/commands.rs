@@ -1,7 +1,169 @@
 use super::{Context, Error};
+use crate::api::{
+    cache::CacheStats,
+    jupiter::jupiter_api_client::JupiterApiClient,
+    moralis::moralis_api_client::MoralisApiClient,
+    rate_limiter::{RateLimit, RateLimiter, RateLimiters},
+};
+use crate::db::database::Database;
+use std::sync::Arc;
 
 #[poise::command(slash_command)]
 pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
     ctx.say("pong").await?;
     Ok(())
 }
+
+// Marks are shared by every guild, so only bot owners may set them.
+/// Marks a scanned token as rugged (or clears the mark) for dev history.
+#[poise::command(slash_command, owners_only)]
+pub async fn rug(
+    ctx: Context<'_>,
+    #[description = "Token mint address"] mint: String,
+    #[description = "Whether the token rugged"] rugged: Option<bool>,
+) -> Result<(), Error> {
+    let database: Arc<Database> = Arc::clone(
+        ctx.serenity_context()
+            .data
+            .read()
+            .await
+            .get::<Database>()
+            .expect("Expected Database in TypeMap"),
+    );
+
+    let rugged = rugged.unwrap_or(true);
+
+    if database.set_token_rugged(&mint, rugged).await? {
+        let status = if rugged { "rugged" } else { "not rugged" };
+        ctx.say(format!("`{}` marked as {}", mint, status)).await?;
+    } else {
+        ctx.say(format!("`{}` has not been scanned yet", mint))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Shows response cache hit/miss counters per upstream.
+#[poise::command(slash_command, owners_only)]
+pub async fn cache(ctx: Context<'_>) -> Result<(), Error> {
+    let (moralis_stats, jupiter_stats) = {
+        let data = ctx.serenity_context().data.read().await;
+        (
+            data.get::<MoralisApiClient>()
+                .expect("Expected Moralis Api Client in TypeMap")
+                .cache_stats(),
+            data.get::<JupiterApiClient>()
+                .expect("Expected Jupiter Api Client in TypeMap")
+                .cache_stats(),
+        )
+    };
+
+    let format_stats = |name: &str, stats: CacheStats| {
+        let lookups = stats.hits + stats.misses;
+        let hit_rate = if lookups == 0 {
+            0.0
+        } else {
+            stats.hits as f64 * 100.0 / lookups as f64
+        };
+
+        format!(
+            "{}: `{}` hits ⋅ `{}` misses ⋅ `{:.1}%` ⋅ `{}` entries",
+            name, stats.hits, stats.misses, hit_rate, stats.entries
+        )
+    };
+
+    ctx.say(format!(
+        "{}\n{}",
+        format_stats("Moralis", moralis_stats),
+        format_stats("Jupiter", jupiter_stats)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Shows request counts and parking state of every Moralis API key.
+#[poise::command(slash_command, owners_only)]
+pub async fn keys(ctx: Context<'_>) -> Result<(), Error> {
+    let key_usage = ctx
+        .serenity_context()
+        .data
+        .read()
+        .await
+        .get::<MoralisApiClient>()
+        .expect("Expected Moralis Api Client in TypeMap")
+        .key_usage();
+
+    let lines: Vec<String> = key_usage
+        .iter()
+        .enumerate()
+        .map(|(index, usage)| {
+            let status = match usage.parked_for {
+                _ if usage.disabled => String::from("disabled (invalid)"),
+                Some(parked_for) => format!("parked {}m", parked_for.as_secs().div_ceil(60)),
+                None => String::from("active"),
+            };
+
+            format!(
+                "#{} `{}`: `{}` requests ⋅ `{}` throttled ⋅ `{}` exhausted ⋅ {}",
+                index, usage.label, usage.requests, usage.throttled, usage.exhausted, status
+            )
+        })
+        .collect();
+
+    ctx.say(lines.join("\n")).await?;
+
+    Ok(())
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum Upstream {
+    Moralis,
+    Jupiter,
+    DexScreener,
+    #[name = "Solana RPC"]
+    SolanaRpc,
+}
+
+// Limits are shared by every guild, so only bot owners may change them.
+/// Shows or changes the request rate limit of an upstream.
+#[poise::command(slash_command, owners_only)]
+pub async fn ratelimit(
+    ctx: Context<'_>,
+    #[description = "Upstream API"] upstream: Upstream,
+    #[description = "Requests per second, 0 disables the limit"] per_second: Option<f64>,
+    #[description = "Requests allowed in a burst"] burst: Option<u32>,
+) -> Result<(), Error> {
+    let rate_limiter: Arc<RateLimiter> = {
+        let data = ctx.serenity_context().data.read().await;
+        let rate_limiters = data
+            .get::<RateLimiters>()
+            .expect("Expected Rate Limiters in TypeMap");
+
+        Arc::clone(match upstream {
+            Upstream::Moralis => &rate_limiters.moralis,
+            Upstream::Jupiter => &rate_limiters.jupiter,
+            Upstream::DexScreener => &rate_limiters.dexscreener,
+            Upstream::SolanaRpc => &rate_limiters.solana_rpc,
+        })
+    };
+
+    let current = rate_limiter.limit();
+
+    if per_second.is_some() || burst.is_some() {
+        rate_limiter.set_limit(RateLimit {
+            per_second: per_second.unwrap_or(current.per_second).max(0.0),
+            burst: burst.unwrap_or(current.burst).max(1),
+        });
+    }
+
+    let limit = rate_limiter.limit();
+    ctx.say(format!(
+        "{:?}: `{}` req/s ⋅ burst `{}`",
+        upstream, limit.per_second, limit.burst
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -1,29 +1,90 @@
 use crate::api::{
-    jupiter::{jupiter_api_client::JupiterApiClient, models::TokenData},
-    models::{SolTokenInfo, TokenLinks, TokenStats1H, TokenStats24H},
-    moralis::{
-        constants::{PUMP_SWAP_ADDRESS, RAYDIUM_CPMM_ADDRESS},
-        models::TokenPair,
-        moralis_api_client::MoralisApiClient,
+    fallback_provider::FallbackTokenDataProvider,
+    jupiter::{
+        jupiter_api_client::JupiterApiClient,
+        models::{FirstPool, TokenData},
     },
+    models::{
+        BondingCurveInfo, Chain, MetadataAuthority, SolTokenInfo, SymbolMatch, TokenAuthorities,
+    },
+    moralis::moralis_evm_api_client::MoralisEvmApiClient,
+    solana_rpc::{
+        accounts::{BondingCurve, MintAccount},
+        constants::WRAPPED_SOL_MINT,
+        solana_rpc_client::SolanaRpcClient,
+    },
+    traits::TokenDataProvider,
 };
 use crate::db::{
     database::Database,
     models::{Guild, NewTokenScan, ScanType, Token, User},
 };
+use crate::errors::{AccountError, AppError, ScanError};
 use crate::utils::{
-    message_parser::{extract_sol_token_address, extract_token_symbol},
+    decimal,
+    message_parser::{TokenAddress, extract_token_address, extract_token_symbol},
+    single_flight::{SharedError, SingleFlight},
     token_message_builder::TokenMessageBuilder,
 };
+use chrono::{DateTime, TimeDelta, Utc};
 use serenity::{
-    all::{Context, EventHandler, Message, Ready},
+    all::{
+        ComponentInteractionDataKind, Context, CreateInteractionResponse, CreateMessage,
+        EditMessage, EventHandler, Message, Ready,
+    },
     async_trait,
 };
-use std::{sync::Arc, time::Instant};
-use tokio::join;
-use tracing::{error, info};
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{join, time::timeout};
+use tracing::{Level, debug, error, info};
+
+/// How long the author of a `$SYMBOL` lookup has to pick a token.
+const SYMBOL_PICK_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct Handler {
+    pub settings: ScanSettings,
+    /// Scans in progress by mint, shared by every message pasting the same one.
+    pub scans: SingleFlight<Result<CoreScan, SharedError>>,
+    /// Slower detail scans in progress by mint, started once the embed is out.
+    pub detail_scans: SingleFlight<SolTokenInfo>,
+}
+
+/// Tunables of the scan pipeline, loaded from config.
+pub struct ScanSettings {
+    pub sniper_slot_window: u64,
+    pub sniper_max_signature_pages: usize,
+    pub fresh_launch_minutes: i64,
+    pub timeouts: ScanTimeouts,
+}
+
+/// How long each source may take before the scan goes on without it.
+#[derive(Debug, Clone, Copy)]
+pub struct ScanTimeouts {
+    pub metadata: Duration,
+    pub pair_stats: Duration,
+    pub holders: Duration,
+    pub jupiter: Duration,
+    pub rpc: Duration,
+    /// Sniper detection and the on-chain launch time, which page through
+    /// the first transactions of the mint.
+    pub launch_analysis: Duration,
+    /// Budget of every provider but the last in a fallback chain, so a slow
+    /// source leaves the fallbacks time to answer within the section timeout.
+    pub provider: Duration,
+}
 
-pub struct Handler;
+/// What the first embed is built from, plus what the detail scan builds on.
+#[derive(Clone)]
+pub struct CoreScan {
+    token_info: SolTokenInfo,
+    pair_address: Option<String>,
+    first_pool: Option<FirstPool>,
+    dev_mints: Option<u32>,
+}
 
 #[async_trait]
 impl EventHandler for Handler {
@@ -51,210 +112,954 @@ impl EventHandler for Handler {
 
         let msg_content = &new_message.content;
 
-        let token_address_opt = match extract_sol_token_address(msg_content)
-            // .or(extract_token_symbol(msg_content)) 
-            {
-                Ok(token_address) => token_address,
-                Err(e) => {
-                    error!("{}", e);
-                    None
-                }
-            };
+        let token_address_opt = match extract_token_address(msg_content) {
+            Ok(token_address) => token_address,
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
 
         if let Some(token_address) = token_address_opt {
-            let data = ctx.data.read().await;
-            let moralis_api_client: Arc<MoralisApiClient> = Arc::clone(
-                data.get::<MoralisApiClient>()
-                    .expect("Expected Moralis Api Client in TypeMap"),
-            );
-            let jupiter_api_client: Arc<JupiterApiClient> = Arc::clone(
-                data.get::<JupiterApiClient>()
-                    .expect("Expected Jupiter Api Client in TypeMap"),
-            );
-            let database = Arc::clone(
-                data.get::<Database>()
-                    .expect("Expected Database in TypeMap"),
-            );
-
-            let start = Instant::now();
-
-            let client = Arc::clone(&moralis_api_client);
-            let token_address1 = token_address.clone();
-            let fut_metadata = async move { client.get_token_metadata(&token_address1).await };
-
-            let client = Arc::clone(&moralis_api_client);
-            let token_address2 = token_address.clone();
-            let fut_pair_stats = async move {
-                let primary_token_pair = client
-                    .get_primary_token_pair_by_address(&token_address2)
-                    .await?;          
-
-                client
-                    .get_token_pair_stats(&primary_token_pair.pair_address)
-                    .await
+            // Answer right away so a slow upstream does not look like a dead bot.
+            let Some(mut reply) = Self::send_placeholder(
+                &ctx,
+                &new_message,
+                format!("🔎 Scanning `{}`…", token_address.address()),
+            )
+            .await
+            else {
+                return;
             };
 
-            let client = Arc::clone(&moralis_api_client);
-            let token_address3 = token_address.clone();
-            let fut_holder_stats = async move { client.get_token_holders(&token_address3).await };
-
-            let client = Arc::clone(&jupiter_api_client);
-            let token_address4 = token_address.clone();
-            let fut_jup_token_data = async move { client.fetch_token_info(&token_address4).await };
-
-            let (
-                token_metadata_res,
-                token_pair_stats_res,
-                token_holder_stats_res,
-                jup_token_data_res,
-            ) = join!(
-                fut_metadata,
-                fut_pair_stats,
-                fut_holder_stats,
-                fut_jup_token_data
-            );
-
-            let jup_token_data = match jup_token_data_res {
-                Ok(token_data) => token_data,
-                Err(e) => {
-                    error!("{}", e);
-                    TokenData::default()
+            self.scan_and_reply(&ctx, &new_message, &mut reply, token_address, guild_id)
+                .await;
+        } else {
+            match extract_token_symbol(msg_content) {
+                Ok(Some(symbol)) => {
+                    self.lookup_symbol(&ctx, &new_message, &symbol, guild_id)
+                        .await
                 }
-            };
+                Ok(None) => {}
+                Err(e) => e.log(),
+            }
+        }
 
-            let token_metadata = match token_metadata_res {
-                Ok(metadata) => metadata,
-                Err(e) => {
-                    error!("{}", e);
-                    return;
+        if msg_content == "!test"
+            && let Err(why) = new_message.reply(&ctx.http, "pong").await
+        {
+            error!("{}", why);
+        }
+    }
+
+    async fn ready(&self, _ctx: Context, _data_about_bot: Ready) {
+        info!("Discord Bot is ready");
+    }
+}
+
+impl Handler {
+    /// Scans the token and edits `reply` into its embed, recording the first
+    /// scan of the guild. The detail scan lands in a second edit.
+    async fn scan_and_reply(
+        &self,
+        ctx: &Context,
+        new_message: &Message,
+        reply: &mut Message,
+        token_address: TokenAddress,
+        guild_id: u64,
+    ) {
+        let address = String::from(token_address.address());
+
+        let core_scan = match self
+            .scans
+            .run(&address, async {
+                match &token_address {
+                    TokenAddress::Solana(mint) => self.scan_token(ctx, mint.clone()).await,
+                    TokenAddress::Evm { address, chain } => {
+                        self.scan_evm_token(ctx, address, *chain).await
+                    }
                 }
-            };
+                .map_err(SharedError::from)
+            })
+            .await
+        {
+            Ok(core_scan) => core_scan,
+            Err(e) => {
+                let e = AppError::from(e);
+                e.log();
+                Self::fail_reply(ctx, reply, &e).await;
+                return;
+            }
+        };
+        let sol_token_info = core_scan.token_info.clone();
 
-            let token_pair_stats = match token_pair_stats_res {
-                Ok(token_pair_stats) => token_pair_stats,
-                Err(e) => {
-                    error!("{}", e);
-                    return;
+        let database = Arc::clone(
+            ctx.data
+                .read()
+                .await
+                .get::<Database>()
+                .expect("Expected Database in TypeMap"),
+        );
+
+        let scan_type = match database
+            .get_token_scan(&sol_token_info.mint, guild_id)
+            .await
+        {
+            Ok(token_scan_opt) => match token_scan_opt {
+                Some(token_scan) => ScanType::Scanned(token_scan),
+                None => {
+                    let token_info = sol_token_info.clone();
+
+                    let guild = Guild { guild_id };
+
+                    let user = User {
+                        user_id: u64::from(new_message.author.id),
+                    };
+
+                    let token = Token {
+                        token_id: token_info.mint,
+                        name: token_info.name,
+                        symbol: token_info.symbol,
+                        dev: token_info.dev,
+                        chain: token_info.chain,
+                    };
+
+                    let new_token_scan = NewTokenScan {
+                        user_id: user.user_id,
+                        guild_id: guild.guild_id,
+                        token_id: token.token_id.clone(),
+                        fdv: token_info.fully_diluted_value,
+                        chain: token.chain,
+                    };
+
+                    let inserted = database
+                        .insert_token_scan(&guild, &user, &token, &new_token_scan)
+                        .await;
+
+                    // Messages for the same token race past the lookup above,
+                    // only the one whose insert lands is the first call.
+                    let existing_scan = match inserted {
+                        Ok(true) => Ok(None),
+                        Ok(false) => database.get_token_scan(&token.token_id, guild_id).await,
+                        Err(e) => Err(e),
+                    };
+
+                    match existing_scan {
+                        Ok(Some(token_scan)) => ScanType::Scanned(token_scan),
+                        Ok(None) => ScanType::FirstScan(new_token_scan),
+                        Err(e) => {
+                            e.log();
+                            Self::fail_reply(ctx, reply, &e).await;
+                            return;
+                        }
+                    }
                 }
-            };
+            },
+            Err(e) => {
+                e.log();
+                Self::fail_reply(ctx, reply, &e).await;
+                return;
+            }
+        };
+
+        if let Err(e) = database
+            .update_token_fdv(&sol_token_info.mint, sol_token_info.fully_diluted_value)
+            .await
+        {
+            e.log();
+        }
+
+        if let Err(e) = Self::render(ctx, reply, new_message, sol_token_info, &scan_type).await {
+            error!("{}", e);
+            return;
+        }
+
+        // Late fields land in a second edit once the detail scan is done.
+        if !core_scan.token_info.details_pending {
+            return;
+        }
+
+        let sol_token_info = self
+            .detail_scans
+            .run(&address, self.scan_details(ctx, core_scan))
+            .await;
+
+        if let Err(e) = Self::render(ctx, reply, new_message, sol_token_info, &scan_type).await {
+            error!("{}", e);
+        }
+    }
+
+    /// Gathers the sections the first embed needs. Only a token without any
+    /// name to show fails the scan, other sections are left empty.
+    async fn scan_token(&self, ctx: &Context, token_address: String) -> Result<CoreScan, AppError> {
+        let data = ctx.data.read().await;
+        let token_data_provider: Arc<FallbackTokenDataProvider> = Arc::clone(
+            data.get::<FallbackTokenDataProvider>()
+                .expect("Expected Token Data Provider in TypeMap"),
+        );
+        let jupiter_api_client: Arc<JupiterApiClient> = Arc::clone(
+            data.get::<JupiterApiClient>()
+                .expect("Expected Jupiter Api Client in TypeMap"),
+        );
+        let solana_rpc_client: Arc<SolanaRpcClient> = Arc::clone(
+            data.get::<SolanaRpcClient>()
+                .expect("Expected Solana Rpc Client in TypeMap"),
+        );
+        drop(data);
+
+        let timeouts = self.settings.timeouts;
+        let start = Instant::now();
+
+        let provider = Arc::clone(&token_data_provider);
+        let token_address1 = token_address.clone();
+        let fut_metadata = Self::timed("Metadata", timeouts.metadata, async move {
+            provider.fetch_metadata(&token_address1).await
+        });
+
+        let provider = Arc::clone(&token_data_provider);
+        let token_address2 = token_address.clone();
+        let fut_pair_stats = Self::timed("Pair stats", timeouts.pair_stats, async move {
+            let primary_token_pair = provider.fetch_primary_pair(&token_address2).await?;
+            let pair_stats = provider.fetch_pair_stats(&primary_token_pair).await?;
+
+            Ok((primary_token_pair.pair_address, pair_stats))
+        });
+
+        let provider = Arc::clone(&token_data_provider);
+        let token_address3 = token_address.clone();
+        let fut_holder_stats = Self::timed("Holders", timeouts.holders, async move {
+            provider.fetch_holders(&token_address3).await
+        });
+
+        let client = Arc::clone(&jupiter_api_client);
+        let token_address4 = token_address.clone();
+        let fut_jup_token_data = Self::timed("Jupiter", timeouts.jupiter, async move {
+            client.fetch_token_info(&token_address4).await
+        });
+
+        let client = Arc::clone(&solana_rpc_client);
+        let token_address5 = token_address.clone();
+        let fut_mint = Self::timed("Mint account", timeouts.rpc, async move {
+            client.get_mint(&token_address5).await
+        });
+
+        let client = Arc::clone(&solana_rpc_client);
+        let token_address6 = token_address.clone();
+        let fut_metaplex_metadata = Self::timed("Metaplex metadata", timeouts.rpc, async move {
+            client.get_metaplex_metadata(&token_address6).await
+        });
+
+        let client = Arc::clone(&solana_rpc_client);
+        let token_address7 = token_address.clone();
+        let fut_bonding_curve = Self::timed("Bonding curve", timeouts.rpc, async move {
+            client.get_pump_fun_bonding_curve(&token_address7).await
+        });
+
+        let (
+            token_metadata_res,
+            token_pair_stats_res,
+            token_holder_stats_res,
+            jup_token_data_res,
+            mint_res,
+            metaplex_metadata_res,
+            bonding_curve_res,
+        ) = join!(
+            fut_metadata,
+            fut_pair_stats,
+            fut_holder_stats,
+            fut_jup_token_data,
+            fut_mint,
+            fut_metaplex_metadata,
+            fut_bonding_curve
+        );
+
+        let mut unavailable_sections = Self::unavailable_sections(&[
+            ("Metadata", token_metadata_res.as_ref().err()),
+            ("Pair stats", token_pair_stats_res.as_ref().err()),
+            ("Holders", token_holder_stats_res.as_ref().err()),
+            ("Jupiter", jup_token_data_res.as_ref().err()),
+            ("Mint account", mint_res.as_ref().err()),
+            ("Metaplex metadata", metaplex_metadata_res.as_ref().err()),
+            ("Bonding curve", bonding_curve_res.as_ref().err()),
+        ]);
+
+        // The metadata chain ends with on-chain metadata, so a failure here
+        // means there is nothing to show.
+        let token_metadata = match token_metadata_res {
+            Ok(metadata) => metadata,
+            Err(e) => return Err(Self::metadata_failure(&token_address, e, &mint_res)),
+        };
+
+        let mint = match mint_res {
+            Ok(mint) => Some(mint),
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
 
-            let token_holder_stats = match token_holder_stats_res {
-                Ok(holder_stats) => holder_stats,
+        let metadata_authority = match metaplex_metadata_res {
+            Ok(metadata) => Some(MetadataAuthority {
+                update_authority: metadata.update_authority,
+                is_mutable: metadata.is_mutable,
+            }),
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
+
+        let active_bonding_curve = match bonding_curve_res {
+            Ok(bonding_curve) => bonding_curve.filter(|curve| !curve.complete),
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
+
+        // Curve figures are denominated in SOL, so they need the SOL price.
+        let bonding_curve = match active_bonding_curve {
+            Some(curve) => match Self::timed(
+                "SOL price",
+                timeouts.jupiter,
+                jupiter_api_client.fetch_token_info(WRAPPED_SOL_MINT),
+            )
+            .await
+            {
+                Ok(TokenData {
+                    usd_price: Some(sol_usd_price),
+                    ..
+                }) => match Self::bonding_curve_info(&curve, sol_usd_price) {
+                    Ok(bonding_curve) => Some(bonding_curve),
+                    Err(e) => {
+                        e.log();
+                        None
+                    }
+                },
+                Ok(_) => {
+                    error!("SOL price missing from Jupiter response");
+                    None
+                }
                 Err(e) => {
-                    error!("{}", e);
-                    return;
+                    unavailable_sections
+                        .extend(Self::unavailable_sections(&[("SOL price", Some(&e))]));
+                    e.log();
+                    None
                 }
-            };
+            },
+            None => None,
+        };
+
+        let jup_token_data = match jup_token_data_res {
+            Ok(token_data) => token_data,
+            Err(e) => {
+                e.log();
+                TokenData::default()
+            }
+        };
 
-            let duration = start.elapsed();
-            println!("{:?}", duration);
+        // Tokens still on the curve have no pool to take stats from.
+        let (pair_address, token_pair_stats) = match token_pair_stats_res {
+            Ok((pair_address, token_pair_stats)) => (Some(pair_address), Some(token_pair_stats)),
+            Err(e) => {
+                if bonding_curve.is_none() {
+                    e.log();
+                }
+                (None, None)
+            }
+        };
 
-            let sol_token_info = SolTokenInfo {
+        let holder_count = match token_holder_stats_res {
+            Ok(holder_stats) => Some(holder_stats.total_holders),
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
+
+        // Live prices come first: the curve, then the mint supply at the pair
+        // price. Metadata FDV is served from a long-lived cache, so it is only
+        // used when neither is known.
+        let fully_diluted_value = bonding_curve
+            .as_ref()
+            .map(|curve| curve.market_cap_usd)
+            .or_else(|| {
+                mint.as_ref()
+                    .zip(token_pair_stats.as_ref())
+                    .and_then(|(mint, pair_stats)| {
+                        decimal::from_token_amount(mint.supply, mint.decimals)
+                            .and_then(|supply| decimal::mul(supply, pair_stats.usd_price))
+                            .inspect_err(AppError::log)
+                            .ok()
+                    })
+            })
+            .or(token_metadata.fully_diluted_value);
+
+        // Token age is anchored on the first pool Jupiter reports. The detail
+        // scan falls back to the first on-chain transaction of the mint.
+        let first_pool = jup_token_data.first_pool.clone();
+        let launched_at = first_pool
+            .as_ref()
+            .map(|first_pool| first_pool.created_at)
+            .or_else(|| {
+                token_pair_stats
+                    .as_ref()
+                    .and_then(|pair_stats| pair_stats.pair_created_at)
+            });
+
+        debug!("{} | core scan took {:?}", token_address, start.elapsed());
+
+        Ok(CoreScan {
+            token_info: SolTokenInfo {
+                chain: Chain::Solana,
                 mint: token_metadata.mint,
                 name: token_metadata.name,
                 symbol: token_metadata.symbol,
                 logo: token_metadata.logo,
-                links: TokenLinks {
-                    discord: token_metadata.links.discord,
-                    telegram: token_metadata.links.telegram,
-                    reddit: token_metadata.links.reddit,
-                    twitter: token_metadata.links.twitter,
-                    website: token_metadata.links.website,
-                },
+                links: Some(token_metadata.links),
                 dev: jup_token_data.dev,
                 launchpad: jup_token_data.launchpad,
-                token_pair_exchange_name: token_pair_stats.exchange,
-                holder_count: token_holder_stats.total_holders,
-                fully_diluted_value: token_metadata.fully_diluted_value.parse::<f64>().unwrap(),
-                liquidity_usd: token_pair_stats.total_iquidity_usd.parse::<f64>().unwrap(),
-                usd_price: token_pair_stats.current_usd_price.parse::<f64>().unwrap(),
-                stats_1h: TokenStats1H {
-                    buys: token_pair_stats.buys.h1,
-                    sells: token_pair_stats.sells.h1,
-                    buy_volume: token_pair_stats.buy_volume.h1,
-                    sell_volume: token_pair_stats.sell_volume.h1,
-                    price_percent_change: token_pair_stats.price_percent_change.h1,
-                },
-                stats_24h: TokenStats24H {
-                    price_percent_change: token_pair_stats.price_percent_change.h24,
-                },
-            };
+                holder_count,
+                fully_diluted_value,
+                pair_stats: token_pair_stats,
+                launched_at,
+                is_fresh: self.is_fresh(launched_at),
+                authorities: mint.as_ref().map(|mint| TokenAuthorities {
+                    mint_authority: mint.mint_authority.clone(),
+                    freeze_authority: mint.freeze_authority.clone(),
+                }),
+                token_program: mint.as_ref().map(|mint| mint.token_program),
+                token_extensions: mint.map(|mint| mint.extensions),
+                metadata_authority,
+                bonding_curve,
+                holder_concentration: None,
+                liquidity_lock: None,
+                snipers: None,
+                dev_profile: None,
+                unavailable_sections,
+                details_pending: true,
+            },
+            pair_address,
+            first_pool,
+            dev_mints: jup_token_data.audit.and_then(|audit| audit.dev_mints),
+        })
+    }
 
-            let scan_type = match database
-                .get_token_scan(&sol_token_info.mint, guild_id)
-                .await
-            {
-                Ok(token_scan_opt) => match token_scan_opt {
-                    Some(token_scan) => ScanType::Scanned(token_scan),
+    /// Scans an EVM token through Moralis once the chain it trades on is
+    /// known. A token without an active pair is still shown from its metadata.
+    /// EVM tokens have no detail scan, the first embed is final.
+    async fn scan_evm_token(
+        &self,
+        ctx: &Context,
+        token_address: &str,
+        hint: Option<Chain>,
+    ) -> Result<CoreScan, AppError> {
+        let data = ctx.data.read().await;
+        let moralis_evm_api_client: Arc<MoralisEvmApiClient> = Arc::clone(
+            data.get::<MoralisEvmApiClient>()
+                .expect("Expected Moralis Evm Api Client in TypeMap"),
+        );
+        drop(data);
+
+        let timeouts = self.settings.timeouts;
+        let client = &moralis_evm_api_client;
+
+        let primary_pair_res = Self::timed(
+            "Pairs",
+            timeouts.pair_stats,
+            client.find_primary_pair(token_address, hint),
+        )
+        .await;
+        let mut unavailable_sections =
+            Self::unavailable_sections(&[("Pairs", primary_pair_res.as_ref().err())]);
+
+        let (chain, token_pair) = match primary_pair_res {
+            Ok((chain, token_pair)) => (chain, Some(token_pair)),
+            Err(e) => {
+                let chain_res = match hint {
+                    Some(chain) => Ok(chain),
                     None => {
-                        let token_info = sol_token_info.clone();
+                        Self::timed("Chain", timeouts.metadata, client.find_chain(token_address))
+                            .await
+                    }
+                };
+                // Without a chain there is no metadata to fall back to either.
+                let Ok(chain) = chain_res else {
+                    return Err(e);
+                };
+                e.log();
+                (chain, None)
+            }
+        };
+
+        let (token_metadata_res, token_pair_stats_res, token_holder_stats_res) = join!(
+            Self::timed(
+                "Metadata",
+                timeouts.metadata,
+                client.fetch_metadata(token_address, chain),
+            ),
+            Self::timed("Pair stats", timeouts.pair_stats, async {
+                match &token_pair {
+                    Some(token_pair) => client.fetch_pair_stats(token_pair, chain).await.map(Some),
+                    None => Ok(None),
+                }
+            }),
+            Self::timed(
+                "Holders",
+                timeouts.holders,
+                client.fetch_holders(token_address, chain),
+            )
+        );
+
+        let token_metadata = token_metadata_res?;
 
-                        let guild = Guild { guild_id };
+        unavailable_sections.extend(Self::unavailable_sections(&[
+            ("Pair stats", token_pair_stats_res.as_ref().err()),
+            ("Holders", token_holder_stats_res.as_ref().err()),
+        ]));
 
-                        let user = User {
-                            user_id: u64::from(new_message.author.id),
-                        };
+        let token_pair_stats = match token_pair_stats_res {
+            Ok(token_pair_stats) => token_pair_stats,
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
 
-                        let token = Token {
-                            token_id: token_info.mint,
-                            name: token_info.name,
-                            symbol: token_info.symbol,
-                        };
+        let holder_count = match token_holder_stats_res {
+            Ok(holder_stats) => Some(holder_stats.total_holders),
+            Err(e) => {
+                e.log();
+                None
+            }
+        };
 
-                        let new_token_scan = NewTokenScan {
-                            user_id: user.user_id,
-                            guild_id: guild.guild_id,
-                            token_id: token.token_id.clone(),
-                            fdv: token_info.fully_diluted_value,
-                        };
+        Ok(CoreScan {
+            token_info: SolTokenInfo {
+                chain,
+                mint: token_metadata.mint.to_lowercase(),
+                name: token_metadata.name,
+                symbol: token_metadata.symbol,
+                logo: token_metadata.logo,
+                links: Some(token_metadata.links),
+                dev: None,
+                launchpad: None,
+                holder_count,
+                fully_diluted_value: token_metadata.fully_diluted_value,
+                pair_stats: token_pair_stats,
+                launched_at: None,
+                is_fresh: false,
+                authorities: None,
+                token_program: None,
+                token_extensions: None,
+                metadata_authority: None,
+                bonding_curve: None,
+                holder_concentration: None,
+                liquidity_lock: None,
+                snipers: None,
+                dev_profile: None,
+                unavailable_sections,
+                details_pending: false,
+            },
+            pair_address: token_pair.map(|token_pair| token_pair.pair_address),
+            first_pool: None,
+            dev_mints: None,
+        })
+    }
 
-                        if let Err(e) = database
-                            .insert_token_scan(&guild, &user, &token, &new_token_scan)
-                            .await
-                        {
-                            error!("{}", e);
-                            return;
-                        }
+    /// Fills in the slower sections: snipers, on-chain launch time, holder
+    /// concentration, liquidity lock and the dev's track record. Sections that
+    /// fail or time out are left empty.
+    async fn scan_details(&self, ctx: &Context, core_scan: CoreScan) -> SolTokenInfo {
+        let data = ctx.data.read().await;
+        let solana_rpc_client: Arc<SolanaRpcClient> = Arc::clone(
+            data.get::<SolanaRpcClient>()
+                .expect("Expected Solana Rpc Client in TypeMap"),
+        );
+        let database = Arc::clone(
+            data.get::<Database>()
+                .expect("Expected Database in TypeMap"),
+        );
+        drop(data);
 
-                        ScanType::FirstScan(new_token_scan)
-                    }
-                },
-                Err(e) => {
+        let CoreScan {
+            mut token_info,
+            pair_address,
+            first_pool,
+            dev_mints,
+        } = core_scan;
+        let timeouts = self.settings.timeouts;
+        let mint = token_info.mint.clone();
+
+        let fut_liquidity_lock = Self::timed("Liquidity lock", timeouts.rpc, async {
+            match &pair_address {
+                Some(pair_address) => solana_rpc_client.get_liquidity_lock(pair_address).await,
+                None => Ok(None),
+            }
+        });
+
+        let fut_snipers = Self::timed(
+            "Snipers",
+            timeouts.launch_analysis,
+            solana_rpc_client.get_launch_snipers(
+                &mint,
+                first_pool.as_ref().map(|first_pool| first_pool.id.as_str()),
+                first_pool.as_ref().map(|first_pool| first_pool.created_at),
+                self.settings.sniper_slot_window,
+                self.settings.sniper_max_signature_pages,
+            ),
+        );
+
+        let fut_launched_at = Self::timed("Launch time", timeouts.launch_analysis, async {
+            match &first_pool {
+                Some(first_pool) => Ok(Some(first_pool.created_at)),
+                None => {
+                    solana_rpc_client
+                        .get_first_signature_time(&mint, self.settings.sniper_max_signature_pages)
+                        .await
+                }
+            }
+        });
+
+        let fut_holder_concentration = Self::timed(
+            "Holder concentration",
+            timeouts.rpc,
+            solana_rpc_client.get_holder_concentration(&mint),
+        );
+
+        let fut_dev_profile = async {
+            match &token_info.dev {
+                Some(dev) => database
+                    .get_dev_profile(dev, &mint, dev_mints)
+                    .await
+                    .map(Some),
+                None => Ok(None),
+            }
+        };
+
+        let (
+            liquidity_lock_res,
+            snipers_res,
+            launched_at_res,
+            holder_concentration_res,
+            dev_profile_res,
+        ) = join!(
+            fut_liquidity_lock,
+            fut_snipers,
+            fut_launched_at,
+            fut_holder_concentration,
+            fut_dev_profile
+        );
+
+        token_info
+            .unavailable_sections
+            .extend(Self::unavailable_sections(&[
+                ("Liquidity lock", liquidity_lock_res.as_ref().err()),
+                ("Snipers", snipers_res.as_ref().err()),
+                ("Launch time", launched_at_res.as_ref().err()),
+                (
+                    "Holder concentration",
+                    holder_concentration_res.as_ref().err(),
+                ),
+            ]));
+
+        match liquidity_lock_res {
+            Ok(liquidity_lock) => token_info.liquidity_lock = liquidity_lock,
+            Err(e) => e.log(),
+        }
+
+        match snipers_res {
+            Ok(snipers) => token_info.snipers = snipers,
+            Err(e) => e.log(),
+        }
+
+        match launched_at_res {
+            Ok(launched_at) => {
+                token_info.launched_at = launched_at.or(token_info.launched_at);
+                token_info.is_fresh = self.is_fresh(token_info.launched_at);
+            }
+            Err(e) => e.log(),
+        }
+
+        match holder_concentration_res {
+            Ok(holder_concentration) => {
+                token_info.holder_concentration = Some(holder_concentration)
+            }
+            Err(e) => e.log(),
+        }
+
+        match dev_profile_res {
+            Ok(dev_profile) => token_info.dev_profile = dev_profile,
+            Err(e) => e.log(),
+        }
+
+        token_info.details_pending = false;
+
+        token_info
+    }
+
+    fn is_fresh(&self, launched_at: Option<DateTime<Utc>>) -> bool {
+        launched_at.is_some_and(|launched_at| {
+            Utc::now().signed_duration_since(launched_at)
+                < TimeDelta::minutes(self.settings.fresh_launch_minutes)
+        })
+    }
+
+    /// Converts the SOL denominated curve figures to USD.
+    fn bonding_curve_info(
+        curve: &BondingCurve,
+        sol_usd_price: f64,
+    ) -> Result<BondingCurveInfo, AppError> {
+        let sol_usd_price = decimal::from_f64(sol_usd_price)?;
+
+        Ok(BondingCurveInfo {
+            progress_percent: curve.progress_percent(),
+            usd_price: decimal::mul(decimal::from_f64(curve.price_in_sol())?, sol_usd_price)?,
+            market_cap_usd: decimal::mul(
+                decimal::from_f64(curve.market_cap_in_sol())?,
+                sol_usd_price,
+            )?,
+            sol_reserves: curve.real_sol_reserves_in_sol(),
+        })
+    }
+
+    /// Sections left empty because their upstream was down, throttled or too
+    /// slow. Sections a fallback source answered for succeeded, and "not found"
+    /// style answers are not an outage, so neither is listed.
+    fn unavailable_sections(sections: &[(&'static str, Option<&AppError>)]) -> Vec<&'static str> {
+        sections
+            .iter()
+            .filter(|(_, error)| error.is_some_and(|e| e.severity() == Level::WARN))
+            .map(|(section, _)| *section)
+            .collect()
+    }
+
+    /// Gives `fut` at most `after` to finish.
+    async fn timed<T>(
+        section: &'static str,
+        after: Duration,
+        fut: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        match timeout(after, fut).await {
+            Ok(result) => result,
+            Err(_) => Err(ScanError::Timeout { section, after }.into()),
+        }
+    }
+
+    /// Edits the reply into the scan embed for `token_info`.
+    async fn render(
+        ctx: &Context,
+        reply: &mut Message,
+        new_message: &Message,
+        token_info: SolTokenInfo,
+        scan_type: &ScanType,
+    ) -> Result<(), anyhow::Error> {
+        let token_msg_builder = TokenMessageBuilder::new(Arc::clone(&ctx.http), token_info);
+        let msg = token_msg_builder
+            .build(&new_message.author, scan_type)
+            .await?;
+
+        reply.edit(&ctx.http, msg).await?;
+
+        Ok(())
+    }
+
+    /// An unknown mint, which has no mint account, reads better than whatever
+    /// the metadata sources answered for it.
+    fn metadata_failure(
+        token_address: &str,
+        error: AppError,
+        mint_res: &Result<MintAccount, AppError>,
+    ) -> AppError {
+        let unknown_mint = mint_res.as_ref().err().is_some_and(|e| {
+            matches!(
+                e.root(),
+                AppError::Account(
+                    AccountError::NotFound { .. } | AccountError::UnexpectedOwner { .. }
+                )
+            )
+        });
+
+        if unknown_mint {
+            return ScanError::UnknownMint {
+                mint: String::from(token_address),
+            }
+            .into();
+        }
+
+        error
+    }
+
+    /// Resolves `$SYMBOL` through Jupiter. A single match is scanned right
+    /// away, several are offered in a select menu and the picked one is scanned.
+    async fn lookup_symbol(
+        &self,
+        ctx: &Context,
+        new_message: &Message,
+        symbol: &str,
+        guild_id: u64,
+    ) {
+        let Some(mut reply) =
+            Self::send_placeholder(ctx, new_message, format!("🔎 Looking up `${}`…", symbol)).await
+        else {
+            return;
+        };
+
+        let matches = match self.symbol_matches(ctx, symbol, guild_id).await {
+            Ok(matches) => matches,
+            Err(e) => {
+                e.log();
+                Self::fail_reply(ctx, &mut reply, &e).await;
+                return;
+            }
+        };
+
+        let mint = if let [symbol_match] = matches.as_slice() {
+            symbol_match.mint.clone()
+        } else {
+            let picker = TokenMessageBuilder::build_symbol_picker(symbol, &matches);
+            if let Err(e) = reply.edit(&ctx.http, picker).await {
+                error!("{}", e);
+                return;
+            }
+
+            let Some(interaction) = reply
+                .await_component_interaction(&ctx.shard)
+                .author_id(new_message.author.id)
+                .timeout(SYMBOL_PICK_TIMEOUT)
+                .await
+            else {
+                let expired = EditMessage::new()
+                    .content(format!("⌛ No token picked for `${}`.", symbol))
+                    .components(vec![]);
+                if let Err(e) = reply.edit(&ctx.http, expired).await {
                     error!("{}", e);
-                    return;
                 }
+                return;
             };
 
-            let token_msg_builder = TokenMessageBuilder::new(Arc::clone(&ctx.http), sol_token_info);
-
-            match token_msg_builder
-                .build(&new_message.author, scan_type)
+            if let Err(e) = interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
                 .await
             {
-                Ok(msg) => {
-                    if let Err(error) = new_message
-                        .channel_id
-                        .send_message(&ctx.http, msg.reference_message(&new_message))
-                        .await
-                    {
-                        error!("{}", error);
-                    }
+                error!("{}", e);
+            }
+
+            match &interaction.data.kind {
+                ComponentInteractionDataKind::StringSelect { values } if !values.is_empty() => {
+                    values[0].clone()
                 }
-                Err(e) => {
-                    error!("{}", e);
+                _ => {
+                    let dismissed = EditMessage::new()
+                        .content(format!("⌛ No token picked for `${}`.", symbol))
+                        .components(vec![]);
+                    if let Err(e) = reply.edit(&ctx.http, dismissed).await {
+                        error!("{}", e);
+                    }
                     return;
                 }
             }
+        };
+
+        let scanning = EditMessage::new()
+            .content(format!("🔎 Scanning `{}`…", mint))
+            .components(vec![]);
+        if let Err(e) = reply.edit(&ctx.http, scanning).await {
+            error!("{}", e);
+            return;
+        }
+
+        self.scan_and_reply(
+            ctx,
+            new_message,
+            &mut reply,
+            TokenAddress::Solana(mint),
+            guild_id,
+        )
+        .await;
+    }
+
+    /// Tokens trading under `symbol`, the ones already scanned in the guild
+    /// first and otherwise in Jupiter's order.
+    async fn symbol_matches(
+        &self,
+        ctx: &Context,
+        symbol: &str,
+        guild_id: u64,
+    ) -> Result<Vec<SymbolMatch>, AppError> {
+        let data = ctx.data.read().await;
+        let jupiter_api_client: Arc<JupiterApiClient> = Arc::clone(
+            data.get::<JupiterApiClient>()
+                .expect("Expected Jupiter Api Client in TypeMap"),
+        );
+        let database = Arc::clone(
+            data.get::<Database>()
+                .expect("Expected Database in TypeMap"),
+        );
+        drop(data);
+
+        let mut matches = Self::timed(
+            "Symbol search",
+            self.settings.timeouts.jupiter,
+            jupiter_api_client.search_symbol(symbol),
+        )
+        .await?;
+
+        if matches.is_empty() {
+            return Err(ScanError::UnknownSymbol {
+                symbol: String::from(symbol),
+            }
+            .into());
+        }
+
+        let mints: Vec<String> = matches
+            .iter()
+            .map(|symbol_match| symbol_match.mint.clone())
+            .collect();
+
+        // Ranking is a nicety, the menu still works without it.
+        match database.get_scanned_token_ids(&mints, guild_id).await {
+            Ok(scanned) => {
+                for symbol_match in &mut matches {
+                    symbol_match.scanned_in_guild = scanned.contains(&symbol_match.mint);
+                }
+                matches.sort_by_key(|symbol_match| !symbol_match.scanned_in_guild);
+            }
+            Err(e) => e.log(),
         }
 
-        if msg_content == "!test" {
-            if let Err(why) = new_message.reply(&ctx.http, "pong").await {
-                error!("{}", why);
+        Ok(matches)
+    }
+
+    async fn send_placeholder(
+        ctx: &Context,
+        new_message: &Message,
+        content: String,
+    ) -> Option<Message> {
+        let placeholder = CreateMessage::new()
+            .content(content)
+            .reference_message(new_message);
+
+        match new_message
+            .channel_id
+            .send_message(&ctx.http, placeholder)
+            .await
+        {
+            Ok(reply) => Some(reply),
+            Err(e) => {
+                error!("{}", e);
+                None
             }
         }
     }
 
-    async fn ready(&self, _ctx: Context, _data_about_bot: Ready) {
-        info!("Discord Bot is ready");
+    /// Replaces the placeholder with an error embed so a failed scan does not
+    /// look stuck.
+    async fn fail_reply(ctx: &Context, reply: &mut Message, app_error: &AppError) {
+        let msg = TokenMessageBuilder::build_error(app_error);
+
+        if let Err(e) = reply.edit(&ctx.http, msg).await {
+            error!("{}", e);
+        }
     }
 }
@@ -1,4 +1,5 @@
-use super::models::{Guild, NewTokenScan, Token, TokenScan, User};
+use super::migrations;
+use super::models::{DevToken, Guild, NewTokenScan, Token, TokenScan, User};
 use super::repositories::{
     guild::PgGuildRepository,
     token::PgTokenRepository,
@@ -6,12 +7,17 @@ use super::repositories::{
     traits::{GuildRepository, TokenRepository, TokenScanRepository, UserRepository},
     user::PgUserRepository,
 };
-use anyhow::Error;
-use deadpool_postgres::{Config, Runtime};
+use crate::api::models::DevProfile;
+use crate::errors::AppError;
+use deadpool_postgres::{Config, Pool, Runtime};
+use rust_decimal::Decimal;
 use std::sync::Arc;
 use tokio_postgres::NoTls;
 
+const NOTABLE_FDV: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
+
 pub struct Database {
+    pool: Pool,
     guild_repository: Arc<dyn GuildRepository>,
     user_repository: Arc<dyn UserRepository>,
     token_repository: Arc<dyn TokenRepository>,
@@ -19,7 +25,7 @@ pub struct Database {
 }
 
 impl Database {
-    pub fn new(db_url: String) -> Result<Self, Error> {
+    pub fn new(db_url: String) -> Result<Self, AppError> {
         let mut config = Config::new();
         config.url = Some(db_url);
         let pool = config.create_pool(Some(Runtime::Tokio1), NoTls)?;
@@ -28,28 +34,35 @@ impl Database {
             user_repository: Arc::new(PgUserRepository::new(pool.clone())),
             token_repository: Arc::new(PgTokenRepository::new(pool.clone())),
             token_scan_repository: Arc::new(PgTokenScanRepository::new(pool.clone())),
+            pool,
         })
     }
 
+    /// Brings the schema up to date, run once at startup.
+    pub async fn migrate(&self) -> Result<(), AppError> {
+        migrations::run(&self.pool).await
+    }
+
+    /// Records the first scan of a token in a guild. Returns `false` when an
+    /// earlier scan, possibly a concurrent one, got there first.
     pub async fn insert_token_scan(
         &self,
         guild: &Guild,
         user: &User,
         token: &Token,
         token_scan: &NewTokenScan,
-    ) -> Result<(), Error> {
+    ) -> Result<bool, AppError> {
         self.guild_repository.insert(guild).await?;
         self.user_repository.insert(user).await?;
         self.token_repository.insert(token).await?;
-        self.token_scan_repository.insert(token_scan).await?;
-        Ok(())
+        self.token_scan_repository.insert(token_scan).await
     }
 
     pub async fn get_token_scan(
         &self,
         token_id: &str,
         guild_id: u64,
-    ) -> Result<Option<TokenScan>, Error> {
+    ) -> Result<Option<TokenScan>, AppError> {
         let scans = self.token_scan_repository.get(token_id, guild_id).await?;
         if let Some(scan) = scans.into_iter().nth(0) {
             Ok(Some(scan))
@@ -57,4 +70,66 @@ impl Database {
             Ok(None)
         }
     }
+
+    /// Which of `token_ids` have been scanned in the guild.
+    pub async fn get_scanned_token_ids(
+        &self,
+        token_ids: &[String],
+        guild_id: u64,
+    ) -> Result<Vec<String>, AppError> {
+        self.token_scan_repository
+            .get_scanned(token_ids, guild_id)
+            .await
+    }
+
+    /// Records the latest FDV of a token. An unknown FDV leaves the stored
+    /// one alone rather than reading as a rug.
+    pub async fn update_token_fdv(
+        &self,
+        token_id: &str,
+        fdv: Option<Decimal>,
+    ) -> Result<(), AppError> {
+        match fdv {
+            Some(fdv) => self.token_repository.update_fdv(token_id, fdv).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn set_token_rugged(&self, token_id: &str, rugged: bool) -> Result<bool, AppError> {
+        self.token_repository.set_rugged(token_id, rugged).await
+    }
+
+    /// Summarises the other tokens `dev` deployed that we have seen scanned.
+    /// `deployed` is the launch count reported upstream, if any.
+    pub async fn get_dev_profile(
+        &self,
+        dev: &str,
+        token_id: &str,
+        deployed: Option<u32>,
+    ) -> Result<DevProfile, AppError> {
+        let tokens: Vec<DevToken> = self
+            .token_repository
+            .get_by_dev(dev)
+            .await?
+            .into_iter()
+            .filter(|token| token.token_id != token_id)
+            .collect();
+
+        let best = tokens
+            .iter()
+            .filter(|token| token.peak_fdv.is_some())
+            .max_by_key(|token| token.peak_fdv);
+
+        Ok(DevProfile {
+            launch_count: deployed.unwrap_or(tokens.len() as u32 + 1),
+            tracked_count: tokens.len(),
+            above_1m_count: tokens
+                .iter()
+                .filter(|token| token.peak_fdv.is_some_and(|fdv| fdv >= NOTABLE_FDV))
+                .count(),
+            rugged_count: tokens.iter().filter(|token| token.rugged).count(),
+            best_peak_fdv: best.and_then(|token| token.peak_fdv),
+            best_last_fdv: best.and_then(|token| token.last_fdv),
+        })
+    }
 }
@@ -0,0 +1,71 @@
+use crate::errors::AppError;
+use deadpool_postgres::Pool;
+use tracing::info;
+
+/// Schema changes in the order they were made, as version, name and SQL.
+/// New migrations only ever go at the end.
+const MIGRATIONS: [(i32, &str, &str); 4] = [
+    (
+        1,
+        "token_dev_history",
+        include_str!("../../migrations/001_token_dev_history.sql"),
+    ),
+    (
+        2,
+        "exact_fdv",
+        include_str!("../../migrations/002_exact_fdv.sql"),
+    ),
+    (
+        3,
+        "token_chain",
+        include_str!("../../migrations/003_token_chain.sql"),
+    ),
+    (
+        4,
+        "nullable_call_fdv",
+        include_str!("../../migrations/004_nullable_call_fdv.sql"),
+    ),
+];
+
+/// Applies every migration not yet recorded in `schema_migrations`, each in
+/// its own transaction together with its record.
+pub async fn run(pool: &Pool) -> Result<(), AppError> {
+    let mut client = pool.get().await?;
+
+    client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )
+        .await?;
+
+    let applied: Vec<i32> = client
+        .query("SELECT version FROM schema_migrations", &[])
+        .await?
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    for (version, name, sql) in MIGRATIONS {
+        if applied.contains(&version) {
+            continue;
+        }
+
+        let transaction = client.transaction().await?;
+        transaction.batch_execute(sql).await?;
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+                &[&version, &name],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        info!("Applied migration {:03}_{}", version, name);
+    }
+
+    Ok(())
+}
@@ -1,15 +1,14 @@
+use crate::api::models::Chain;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use validator::Validate;
 
 #[derive(Debug, Validate, Clone)]
 pub struct TokenScan {
-    pub id: i64,
-    pub guild_id: u64,
     pub user_id: u64,
     #[validate(length(min = 1))]
     pub token_id: String,
-    #[validate(range(min = 0.0))]
-    pub fdv: f64,
+    pub fdv: Option<Decimal>,
     pub scanned_at: DateTime<Utc>,
 }
 
@@ -19,8 +18,8 @@ pub struct NewTokenScan {
     pub user_id: u64,
     #[validate(length(min = 1))]
     pub token_id: String,
-    #[validate(range(min = 0.0))]
-    pub fdv: f64,
+    pub fdv: Option<Decimal>,
+    pub chain: Chain,
 }
 
 #[derive(Debug)]
@@ -36,6 +35,17 @@ pub struct Token {
     pub name: String,
     #[validate(length(max = 20))]
     pub symbol: String,
+    pub dev: Option<String>,
+    pub chain: Chain,
+}
+
+/// A token from the `tokens` table deployed by a given dev wallet.
+#[derive(Debug, Clone)]
+pub struct DevToken {
+    pub token_id: String,
+    pub peak_fdv: Option<Decimal>,
+    pub last_fdv: Option<Decimal>,
+    pub rugged: bool,
 }
 
 #[derive(Debug)]
@@ -1,6 +1,6 @@
 use super::traits::GuildRepository;
 use crate::db::models::Guild;
-use anyhow::Error;
+use crate::errors::AppError;
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
 
@@ -10,7 +10,7 @@ pub struct PgGuildRepository {
 
 #[async_trait]
 impl GuildRepository for PgGuildRepository {
-    async fn insert(&self, guild: &Guild) -> Result<(), Error> {
+    async fn insert(&self, guild: &Guild) -> Result<(), AppError> {
         let client = self.pool.get().await?;
         client.execute(
             "INSERT INTO guilds (guild_id) VALUES ($1) ON CONFLICT ON CONSTRAINT guilds_pkey DO NOTHING",
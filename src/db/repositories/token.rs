@@ -1,8 +1,9 @@
 use super::traits::TokenRepository;
-use crate::db::models::Token;
-use anyhow::Error;
+use crate::db::models::{DevToken, Token};
+use crate::errors::AppError;
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
+use rust_decimal::Decimal;
 
 pub struct PgTokenRepository {
     pool: Pool,
@@ -10,15 +11,59 @@ pub struct PgTokenRepository {
 
 #[async_trait]
 impl TokenRepository for PgTokenRepository {
-    async fn insert(&self, token: &Token) -> Result<(), Error> {
+    async fn insert(&self, token: &Token) -> Result<(), AppError> {
         let client = self.pool.get().await?;
         client.execute(
-            "INSERT INTO tokens (token_id, name, symbol) VALUES ($1, $2, $3) ON CONFLICT ON CONSTRAINT tokens_pkey DO NOTHING",
-            &[&token.token_id, &token.name, &token.symbol],
+            "INSERT INTO tokens (token_id, name, symbol, dev, chain) VALUES ($1, $2, $3, $4, $5) ON CONFLICT ON CONSTRAINT tokens_pkey DO UPDATE SET dev = COALESCE(tokens.dev, EXCLUDED.dev)",
+            &[&token.token_id, &token.name, &token.symbol, &token.dev, &token.chain.id()],
         ).await?;
 
         Ok(())
     }
+
+    async fn update_fdv(&self, token_id: &str, fdv: Decimal) -> Result<(), AppError> {
+        let client = self.pool.get().await?;
+        client.execute(
+            "UPDATE tokens SET last_fdv = $2, peak_fdv = GREATEST(COALESCE(peak_fdv, 0), $2) WHERE token_id = $1",
+            &[&token_id, &fdv],
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn set_rugged(&self, token_id: &str, rugged: bool) -> Result<bool, AppError> {
+        let client = self.pool.get().await?;
+        let updated = client
+            .execute(
+                "UPDATE tokens SET rugged = $2 WHERE token_id = $1",
+                &[&token_id, &rugged],
+            )
+            .await?;
+
+        Ok(updated > 0)
+    }
+
+    async fn get_by_dev(&self, dev: &str) -> Result<Vec<DevToken>, AppError> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT token_id, peak_fdv, last_fdv, rugged FROM tokens WHERE dev = $1",
+                &[&dev],
+            )
+            .await?;
+
+        let tokens: Vec<DevToken> = rows
+            .into_iter()
+            .map(|row| DevToken {
+                token_id: row.get(0),
+                peak_fdv: row.get(1),
+                last_fdv: row.get(2),
+                rugged: row.get(3),
+            })
+            .collect();
+
+        Ok(tokens)
+    }
 }
 
 impl PgTokenRepository {
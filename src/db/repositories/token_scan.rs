@@ -1,6 +1,6 @@
 use super::traits::TokenScanRepository;
 use crate::db::models::{NewTokenScan, TokenScan};
-use anyhow::Error;
+use crate::errors::AppError;
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
 
@@ -10,26 +10,27 @@ pub struct PgTokenScanRepository {
 
 #[async_trait]
 impl TokenScanRepository for PgTokenScanRepository {
-    async fn insert(&self, token_scan: &NewTokenScan) -> Result<(), Error> {
+    async fn insert(&self, token_scan: &NewTokenScan) -> Result<bool, AppError> {
         let client = self.pool.get().await?;
-        client.execute(
-            "INSERT INTO token_scans (guild_id, user_id, token_id, fdv) VALUES ($1, $2, $3, $4) ON CONFLICT ON CONSTRAINT idx_unique_token_guild DO NOTHING",
+        let inserted = client.execute(
+            "INSERT INTO token_scans (guild_id, user_id, token_id, fdv, chain) VALUES ($1, $2, $3, $4, $5) ON CONFLICT ON CONSTRAINT idx_unique_token_guild DO NOTHING",
             &[
                 &(token_scan.guild_id as i64),
                 &(token_scan.user_id as i64),
                 &token_scan.token_id,
                 &token_scan.fdv,
+                &token_scan.chain.id(),
             ],
         ).await?;
 
-        Ok(())
+        Ok(inserted == 1)
     }
 
-    async fn get(&self, token_id: &str, guild_id: u64) -> Result<Vec<TokenScan>, Error> {
+    async fn get(&self, token_id: &str, guild_id: u64) -> Result<Vec<TokenScan>, AppError> {
         let client = self.pool.get().await?;
         let rows = client
             .query(
-                "SELECT * FROM token_scans WHERE token_id = $1 and guild_id = $2",
+                "SELECT user_id, token_id, fdv, scanned_at FROM token_scans WHERE token_id = $1 and guild_id = $2",
                 &[&token_id, &(guild_id as i64)],
             )
             .await?;
@@ -37,17 +38,31 @@ impl TokenScanRepository for PgTokenScanRepository {
         let scans: Vec<TokenScan> = rows
             .into_iter()
             .map(|row| TokenScan {
-                id: row.get(0),
-                guild_id: row.get::<_, i64>(1) as u64,
-                user_id: row.get::<_, i64>(2) as u64,
-                token_id: row.get(3),
-                fdv: row.get(4),
-                scanned_at: row.get(5),
+                user_id: row.get::<_, i64>(0) as u64,
+                token_id: row.get(1),
+                fdv: row.get(2),
+                scanned_at: row.get(3),
             })
             .collect();
 
         return Ok(scans);
     }
+
+    async fn get_scanned(
+        &self,
+        token_ids: &[String],
+        guild_id: u64,
+    ) -> Result<Vec<String>, AppError> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT DISTINCT token_id FROM token_scans WHERE token_id = ANY($1) and guild_id = $2",
+                &[&token_ids, &(guild_id as i64)],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
 }
 
 impl PgTokenScanRepository {
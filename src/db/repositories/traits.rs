@@ -1,24 +1,34 @@
-use crate::db::models::{Guild, NewTokenScan, Token, TokenScan, User};
-use anyhow::Error;
+use crate::db::models::{DevToken, Guild, NewTokenScan, Token, TokenScan, User};
+use crate::errors::AppError;
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 
 #[async_trait]
 pub trait TokenScanRepository: Send + Sync {
-    async fn insert(&self, token_scan: &NewTokenScan) -> Result<(), Error>;
-    async fn get(&self, token_id: &str, guild_id: u64) -> Result<Vec<TokenScan>, Error>;
+    /// Returns `false` when the token was already scanned in the guild.
+    async fn insert(&self, token_scan: &NewTokenScan) -> Result<bool, AppError>;
+    async fn get(&self, token_id: &str, guild_id: u64) -> Result<Vec<TokenScan>, AppError>;
+    async fn get_scanned(
+        &self,
+        token_ids: &[String],
+        guild_id: u64,
+    ) -> Result<Vec<String>, AppError>;
 }
 
 #[async_trait]
 pub trait GuildRepository: Send + Sync {
-    async fn insert(&self, guild: &Guild) -> Result<(), Error>;
+    async fn insert(&self, guild: &Guild) -> Result<(), AppError>;
 }
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    async fn insert(&self, user: &User) -> Result<(), Error>;
+    async fn insert(&self, user: &User) -> Result<(), AppError>;
 }
 
 #[async_trait]
 pub trait TokenRepository: Send + Sync {
-    async fn insert(&self, token: &Token) -> Result<(), Error>;
+    async fn insert(&self, token: &Token) -> Result<(), AppError>;
+    async fn update_fdv(&self, token_id: &str, fdv: Decimal) -> Result<(), AppError>;
+    async fn set_rugged(&self, token_id: &str, rugged: bool) -> Result<bool, AppError>;
+    async fn get_by_dev(&self, dev: &str) -> Result<Vec<DevToken>, AppError>;
 }
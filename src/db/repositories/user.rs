@@ -1,6 +1,6 @@
 use super::traits::UserRepository;
 use crate::db::models::User;
-use anyhow::Error;
+use crate::errors::AppError;
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
 
@@ -10,7 +10,7 @@ pub struct PgUserRepository {
 
 #[async_trait]
 impl UserRepository for PgUserRepository {
-    async fn insert(&self, user: &User) -> Result<(), Error> {
+    async fn insert(&self, user: &User) -> Result<(), AppError> {
         let client = self.pool.get().await?;
         client
             .execute("INSERT INTO users (user_id) VALUES ($1) ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING", &[&(user.user_id as i64)])
@@ -1,12 +1,169 @@
+use std::{
+    num::{ParseFloatError, ParseIntError},
+    time::Duration,
+};
 use thiserror::Error;
+use tracing::{Level, error, info, warn};
 use crate::api::errors::ApiError;
+use crate::utils::single_flight::SharedError;
 
+/// Error type of the API, DB and parsing layers.
 #[derive(Debug, Error)]
 pub enum AppError {
 	#[error("Api Error {0}")]
 	Api(#[from] ApiError),
 	#[error("Token Pair Error {0}")]
-	TokenPair(#[from] TokenPairError)
+	TokenPair(#[from] TokenPairError),
+	#[error("Provider Error {0}")]
+	Provider(#[from] ProviderError),
+	#[error("Account Error {0}")]
+	Account(#[from] AccountError),
+	#[error("Scan Error {0}")]
+	Scan(#[from] ScanError),
+	#[error("Chain Error {0}")]
+	Chain(#[from] ChainError),
+	/// Connection failures and timeouts talking to an upstream.
+	#[error("Request Error {0}")]
+	Request(#[from] reqwest::Error),
+	#[error("Json Decode Error {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("Number Decode Error {0}")]
+	ParseFloat(#[from] ParseFloatError),
+	#[error("Integer Decode Error {0}")]
+	ParseInt(#[from] ParseIntError),
+	#[error("Decimal Decode Error {0}")]
+	Decimal(#[from] rust_decimal::Error),
+	#[error("Base64 Decode Error {0}")]
+	Base64(#[from] base64::DecodeError),
+	#[error("Base58 Decode Error {0}")]
+	Base58(#[from] bs58::decode::Error),
+	#[error("Validation Error {0}")]
+	Validation(#[from] validator::ValidationErrors),
+	#[error("Database Error {0}")]
+	Database(#[from] tokio_postgres::Error),
+	#[error("Database Pool Error {0}")]
+	Pool(#[from] deadpool_postgres::PoolError),
+	#[error("Database Pool Error {0}")]
+	CreatePool(#[from] deadpool_postgres::CreatePoolError),
+	#[error("Invalid Header {0}")]
+	InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+	#[error("Regex Error {0}")]
+	Regex(#[from] regex::Error),
+	#[error("Io Error {0}")]
+	Io(#[from] std::io::Error),
+	/// Result of a request shared between concurrent callers.
+	#[error(transparent)]
+	Shared(#[from] SharedError),
+}
+
+impl AppError {
+    /// The error itself, looking through the wrapper of shared requests.
+    pub fn root(&self) -> &AppError {
+        match self {
+            Self::Shared(shared) => shared.inner().root(),
+            _ => self,
+        }
+    }
+
+    /// `INFO` for answers like "not indexed yet", `WARN` for upstream trouble
+    /// and `ERROR` for anything that needs fixing on our side.
+    pub fn severity(&self) -> Level {
+        match self.root() {
+            Self::Api(api_error) => match api_error {
+                ApiError::NotFound { .. } => Level::INFO,
+                ApiError::TooManyRequests { .. }
+                | ApiError::InternalServerError { .. }
+                | ApiError::ServiceUnavailable { .. }
+                | ApiError::BadGateway { .. }
+                | ApiError::GatewayTimeout { .. } => Level::WARN,
+                ApiError::Unknown { error_data } if error_data.status_code >= 500 => Level::WARN,
+                _ => Level::ERROR,
+            },
+            Self::TokenPair(_) => Level::INFO,
+            Self::Provider(ProviderError::Unsupported { .. }) => Level::INFO,
+            Self::Account(AccountError::NotFound { .. } | AccountError::UnexpectedOwner { .. }) => {
+                Level::INFO
+            }
+            Self::Scan(ScanError::UnknownMint { .. } | ScanError::UnknownSymbol { .. }) => {
+                Level::INFO
+            }
+            Self::Scan(ScanError::Timeout { .. }) | Self::Request(_) => Level::WARN,
+            _ => Level::ERROR,
+        }
+    }
+
+    /// Short explanation fit for chat.
+    pub fn user_message(&self) -> String {
+        match self.root() {
+            Self::Api(api_error) => {
+                let source = api_error.error_data().source.trim_end_matches(" Api");
+
+                match api_error {
+                    ApiError::NotFound { .. } => format!("Not indexed by {} yet.", source),
+                    ApiError::TooManyRequests { .. } => {
+                        format!("{} is rate limiting us, try again shortly.", source)
+                    }
+                    ApiError::Unauthorized { .. }
+                    | ApiError::Forbidden { .. }
+                    | ApiError::InvalidAPIKey { .. } => {
+                        format!("{} rejected our API key.", source)
+                    }
+                    ApiError::BadRequest { .. } => format!("{} rejected the request.", source),
+                    _ if self.severity() == Level::WARN => {
+                        format!("{} is down right now, try again shortly.", source)
+                    }
+                    _ => format!("{} sent an unexpected response.", source),
+                }
+            }
+            Self::TokenPair(_) => String::from("No active trading pair yet."),
+            Self::Provider(_) => String::from("No data source is configured for this."),
+            Self::Account(AccountError::NotFound { address }) => {
+                format!("`{}` does not exist on-chain.", address)
+            }
+            Self::Account(AccountError::UnexpectedOwner { address, .. }) => {
+                format!("`{}` is not a token mint.", address)
+            }
+            Self::Scan(ScanError::Timeout { section, .. }) => {
+                format!("{} took too long to answer, try again shortly.", section)
+            }
+            Self::Scan(ScanError::UnknownMint { mint }) => {
+                format!("`{}` is not a token mint on Solana.", mint)
+            }
+            Self::Scan(ScanError::UnknownSymbol { symbol }) => {
+                format!("No Solana token trades as `${}`.", symbol)
+            }
+            Self::Request(e) => {
+                let host = e.url().and_then(|url| url.host_str()).unwrap_or("An upstream");
+
+                if e.is_timeout() {
+                    format!("{} timed out, try again shortly.", host)
+                } else {
+                    format!("{} could not be reached, try again shortly.", host)
+                }
+            }
+            Self::Account(AccountError::InvalidData { .. })
+            | Self::Json(_)
+            | Self::ParseFloat(_)
+            | Self::ParseInt(_)
+            | Self::Decimal(_)
+            | Self::Base64(_)
+            | Self::Base58(_)
+            | Self::Validation(_) => String::from("An upstream sent data we could not read."),
+            Self::Database(_) | Self::Pool(_) | Self::CreatePool(_) => {
+                String::from("Our database is unavailable right now.")
+            }
+            _ => String::from("Something went wrong on our side."),
+        }
+    }
+
+    /// Logs the error at its severity.
+    pub fn log(&self) {
+        match self.severity() {
+            Level::INFO => info!("{}", self),
+            Level::WARN => warn!("{}", self),
+            _ => error!("{}", self),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -15,4 +172,64 @@ pub enum TokenPairError {
     ActivePairNotFound {
 		token_address: String
 	},
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("Unknown data source \"{}\"", name)]
+    UnknownSource {
+		name: String
+	},
+    #[error("{} does not provide {}", provider, field)]
+    Unsupported {
+		provider: String,
+		field: String
+	},
+    #[error("No data sources configured for {}", field)]
+    NoSources {
+		field: String
+	},
+}
+
+#[derive(Debug, Error)]
+pub enum AccountError {
+    #[error("Account not found {}", address)]
+    NotFound {
+		address: String
+	},
+    #[error("Account {} is owned by unexpected program {}", address, owner)]
+    UnexpectedOwner {
+		address: String,
+		owner: String
+	},
+    #[error("Invalid account data {}: {}", address, reason)]
+    InvalidData {
+		address: String,
+		reason: String
+	},
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ScanError {
+    #[error("{} timed out after {:?}", section, after)]
+    Timeout {
+		section: &'static str,
+		after: Duration
+	},
+    #[error("Unknown mint {}", mint)]
+    UnknownMint {
+		mint: String
+	},
+    #[error("No token with symbol {}", symbol)]
+    UnknownSymbol {
+		symbol: String
+	},
+}
+
+#[derive(Debug, Error)]
+pub enum ChainError {
+    #[error("Unknown chain \"{}\"", name)]
+    Unknown {
+		name: String
+	},
+}
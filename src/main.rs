@@ -1,18 +1,53 @@
 use anyhow::{Error, Result};
+use api::{
+    circuit_breaker::CircuitBreakerSettings,
+    dexscreener::constants::DEXSCREENER_BASE_URL,
+    fixtures::FixtureMode,
+    jupiter::constants::JUPITER_BASE_URL,
+    moralis::constants::{MORALIS_BASE_URL, MORALIS_EVM_BASE_URL},
+    rate_limiter::RateLimit,
+    retry::RetryPolicy,
+    solana_rpc::constants::SOLANA_RPC_URL,
+};
+use bot::handlers::ScanTimeouts;
 use dotenv::dotenv;
-use std::env;
+use std::{env, fs, str::FromStr, time::Duration};
 use tracing::{Level, info};
 use tracing_subscriber::FmtSubscriber;
 
 mod api;
 mod bot;
 mod db;
-mod utils;
 mod errors;
+mod utils;
 
 struct Config {
     discord_token: String,
-    moralis_api_key: String,
+    moralis_api_keys: Vec<String>,
+    moralis_base_url: String,
+    moralis_evm_base_url: String,
+    jupiter_base_url: String,
+    dexscreener_base_url: String,
+    solana_rpc_url: String,
+    metadata_sources: Vec<String>,
+    primary_pair_sources: Vec<String>,
+    pair_stats_sources: Vec<String>,
+    holders_sources: Vec<String>,
+    sniper_slot_window: u64,
+    sniper_max_signature_pages: usize,
+    fresh_launch_minutes: i64,
+    scan_timeouts: ScanTimeouts,
+    moralis_retry_policy: RetryPolicy,
+    jupiter_retry_policy: RetryPolicy,
+    dexscreener_retry_policy: RetryPolicy,
+    solana_rpc_retry_policy: RetryPolicy,
+    moralis_rate_limit: RateLimit,
+    jupiter_rate_limit: RateLimit,
+    dexscreener_rate_limit: RateLimit,
+    solana_rpc_rate_limit: RateLimit,
+    circuit_breaker: CircuitBreakerSettings,
+    fixture_mode: FixtureMode,
+    fixture_dir: String,
 }
 
 impl Config {
@@ -22,13 +57,151 @@ impl Config {
         let discord_token = env::var("DISCORD_TOKEN")
             .expect("Missing `DISCORD_TOKEN` env var, see README for more information.");
 
-        let moralis_api_key = env::var("MORALIS_API_KEY").expect("Missing Moralis Api Key");
+        let fixture_mode = FixtureMode::from_str(&env::var("FIXTURE_MODE").unwrap_or_default())?;
+
+        let moralis_api_keys = Self::moralis_api_keys()?;
+        // Replays never reach Moralis, so they need no key.
+        if moralis_api_keys.is_empty() && fixture_mode != FixtureMode::Replay {
+            return Err(Error::msg("Missing Moralis Api Key"));
+        }
 
         Ok(Self {
             discord_token,
-            moralis_api_key,
+            moralis_api_keys,
+            moralis_base_url: env::var("MORALIS_BASE_URL")
+                .unwrap_or_else(|_| String::from(MORALIS_BASE_URL)),
+            moralis_evm_base_url: env::var("MORALIS_EVM_BASE_URL")
+                .unwrap_or_else(|_| String::from(MORALIS_EVM_BASE_URL)),
+            jupiter_base_url: env::var("JUPITER_BASE_URL")
+                .unwrap_or_else(|_| String::from(JUPITER_BASE_URL)),
+            dexscreener_base_url: env::var("DEXSCREENER_BASE_URL")
+                .unwrap_or_else(|_| String::from(DEXSCREENER_BASE_URL)),
+            solana_rpc_url: env::var("SOLANA_RPC_URL")
+                .unwrap_or_else(|_| String::from(SOLANA_RPC_URL)),
+            metadata_sources: Self::sources(
+                "TOKEN_METADATA_SOURCES",
+                "moralis,dexscreener,jupiter,rpc",
+            ),
+            primary_pair_sources: Self::sources(
+                "TOKEN_PAIR_SOURCES",
+                "dexscreener,moralis,jupiter",
+            ),
+            pair_stats_sources: Self::sources(
+                "TOKEN_PAIR_STATS_SOURCES",
+                "dexscreener,moralis,jupiter",
+            ),
+            holders_sources: Self::sources("TOKEN_HOLDERS_SOURCES", "moralis,jupiter"),
+            sniper_slot_window: Self::number("SNIPER_SLOT_WINDOW", 3)?,
+            sniper_max_signature_pages: Self::number("SNIPER_MAX_SIGNATURE_PAGES", 5)?,
+            fresh_launch_minutes: Self::number("FRESH_LAUNCH_MINUTES", 30)?,
+            scan_timeouts: ScanTimeouts {
+                metadata: Self::timeout("METADATA_TIMEOUT_MS", 5_000)?,
+                pair_stats: Self::timeout("PAIR_STATS_TIMEOUT_MS", 5_000)?,
+                holders: Self::timeout("HOLDERS_TIMEOUT_MS", 5_000)?,
+                jupiter: Self::timeout("JUPITER_TIMEOUT_MS", 5_000)?,
+                rpc: Self::timeout("RPC_TIMEOUT_MS", 8_000)?,
+                launch_analysis: Self::timeout("LAUNCH_ANALYSIS_TIMEOUT_MS", 20_000)?,
+                provider: Self::timeout("PROVIDER_TIMEOUT_MS", 2_000)?,
+            },
+            moralis_retry_policy: Self::retry_policy("MORALIS")?,
+            jupiter_retry_policy: Self::retry_policy("JUPITER")?,
+            dexscreener_retry_policy: Self::retry_policy("DEXSCREENER")?,
+            solana_rpc_retry_policy: Self::retry_policy("SOLANA_RPC")?,
+            moralis_rate_limit: Self::rate_limit("MORALIS", 20.0, 20)?,
+            jupiter_rate_limit: Self::rate_limit("JUPITER", 10.0, 10)?,
+            dexscreener_rate_limit: Self::rate_limit("DEXSCREENER", 5.0, 10)?,
+            solana_rpc_rate_limit: Self::rate_limit("SOLANA_RPC", 10.0, 20)?,
+            circuit_breaker: CircuitBreakerSettings {
+                failure_threshold: Self::number::<u32>("CIRCUIT_BREAKER_FAILURES", 5)?.max(1),
+                open_duration: Duration::from_secs(Self::number("CIRCUIT_BREAKER_OPEN_SECS", 30)?),
+            },
+            fixture_mode,
+            fixture_dir: env::var("FIXTURE_DIR").unwrap_or_else(|_| String::from("fixtures")),
+        })
+    }
+
+    fn number<T: FromStr>(var: &str, default: T) -> Result<T, Error>
+    where
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match env::var(var) {
+            Ok(value) => Ok(value.trim().parse::<T>()?),
+            Err(_) => Ok(default),
+        }
+    }
+
+    fn timeout(var: &str, default_ms: u64) -> Result<Duration, Error> {
+        Ok(Duration::from_millis(Self::number(var, default_ms)?))
+    }
+
+    /// Reads Moralis keys from the file named by `MORALIS_API_KEYS_FILE` (one per
+    /// line, `#` comments allowed), else from the comma-separated `MORALIS_API_KEYS`,
+    /// else from the single `MORALIS_API_KEY`.
+    fn moralis_api_keys() -> Result<Vec<String>, Error> {
+        let keys: Vec<String> = if let Ok(path) = env::var("MORALIS_API_KEYS_FILE") {
+            fs::read_to_string(path)?
+                .lines()
+                .map(|line| String::from(line.trim()))
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .collect()
+        } else {
+            env::var("MORALIS_API_KEYS")
+                .or_else(|_| env::var("MORALIS_API_KEY"))
+                .unwrap_or_default()
+                .split(',')
+                .map(|key| String::from(key.trim()))
+                .filter(|key| !key.is_empty())
+                .collect()
+        };
+
+        Ok(keys)
+    }
+
+    /// Reads `<PREFIX>_RETRY_ATTEMPTS`, `<PREFIX>_RETRY_BASE_DELAY_MS`,
+    /// `<PREFIX>_RETRY_MAX_DELAY_MS` and `<PREFIX>_REQUEST_TIMEOUT_MS`, falling
+    /// back to the default policy.
+    fn retry_policy(prefix: &str) -> Result<RetryPolicy, Error> {
+        let default = RetryPolicy::default();
+
+        Ok(RetryPolicy {
+            max_attempts: Self::number(
+                &format!("{}_RETRY_ATTEMPTS", prefix),
+                default.max_attempts,
+            )?
+            .max(1),
+            base_delay: Duration::from_millis(Self::number(
+                &format!("{}_RETRY_BASE_DELAY_MS", prefix),
+                default.base_delay.as_millis() as u64,
+            )?),
+            max_delay: Duration::from_millis(Self::number(
+                &format!("{}_RETRY_MAX_DELAY_MS", prefix),
+                default.max_delay.as_millis() as u64,
+            )?),
+            attempt_timeout: Duration::from_millis(Self::number(
+                &format!("{}_REQUEST_TIMEOUT_MS", prefix),
+                default.attempt_timeout.as_millis() as u64,
+            )?),
         })
     }
+
+    /// Reads `<PREFIX>_RATE_LIMIT` (requests per second) and `<PREFIX>_RATE_BURST`.
+    fn rate_limit(prefix: &str, per_second: f64, burst: u32) -> Result<RateLimit, Error> {
+        Ok(RateLimit {
+            per_second: Self::number(&format!("{}_RATE_LIMIT", prefix), per_second)?,
+            burst: Self::number(&format!("{}_RATE_BURST", prefix), burst)?,
+        }
+        .clamped())
+    }
+
+    /// Reads a comma-separated, ordered list of data source names.
+    fn sources(var: &str, default: &str) -> Vec<String> {
+        env::var(var)
+            .unwrap_or_else(|_| String::from(default))
+            .split(',')
+            .map(|source| source.trim().to_lowercase())
+            .filter(|source| !source.is_empty())
+            .collect()
+    }
 }
 
 #[tokio::main]
@@ -41,7 +214,7 @@ async fn main() -> Result<(), Error> {
     let cfg = Config::load().await?;
 
     info!("Running discord bot");
-    bot::run(cfg.discord_token, cfg.moralis_api_key).await?;
+    bot::run(cfg).await?;
 
     Ok(())
 }
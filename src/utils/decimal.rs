@@ -0,0 +1,38 @@
+use crate::errors::AppError;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Parses a decimal string as sent by upstreams, including scientific notation
+/// like `"1.2e-7"`.
+pub fn parse(value: &str) -> Result<Decimal, AppError> {
+    let value = value.trim();
+
+    Ok(Decimal::from_str(value).or_else(|_| Decimal::from_scientific(value))?)
+}
+
+/// Converts a JSON number through its shortest decimal form, so `0.1` stays
+/// `0.1` instead of picking up binary rounding noise. Fails on NaN, infinity
+/// and values out of range.
+pub fn from_f64(value: f64) -> Result<Decimal, AppError> {
+    parse(&value.to_string())
+}
+
+/// `from_f64` for optional fields, where a missing value is not an error.
+pub fn from_opt_f64(value: Option<f64>) -> Result<Option<Decimal>, AppError> {
+    value.map(from_f64).transpose()
+}
+
+/// A raw on-chain amount in whole tokens, e.g. `1_500_000` with 6 decimals is
+/// `1.5`.
+pub fn from_token_amount(amount: u64, decimals: u8) -> Result<Decimal, AppError> {
+    Ok(Decimal::try_from_i128_with_scale(
+        i128::from(amount),
+        u32::from(decimals),
+    )?)
+}
+
+/// Multiplies two amounts, failing instead of panicking on overflow.
+pub fn mul(a: Decimal, b: Decimal) -> Result<Decimal, AppError> {
+    a.checked_mul(b)
+        .ok_or_else(|| rust_decimal::Error::ExceedsMaximumPossibleValue.into())
+}
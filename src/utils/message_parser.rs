@@ -1,7 +1,41 @@
-use anyhow::Error;
+use crate::api::models::Chain;
+use crate::errors::AppError;
 use regex::Regex;
 
-pub fn extract_sol_token_address(msg: &str) -> Result<Option<String>, Error> {
+/// A token address pasted in a message.
+pub enum TokenAddress {
+    Solana(String),
+    /// EVM addresses look the same on every chain, so the chain is only known
+    /// when the message names it.
+    Evm {
+        address: String,
+        chain: Option<Chain>,
+    },
+}
+
+impl TokenAddress {
+    pub fn address(&self) -> &str {
+        match self {
+            Self::Solana(address) => address,
+            Self::Evm { address, .. } => address,
+        }
+    }
+}
+
+/// EVM addresses are checked first, since the hex part of one can also pass
+/// for a base58 Solana address.
+pub fn extract_token_address(msg: &str) -> Result<Option<TokenAddress>, AppError> {
+    if let Some(address) = extract_evm_token_address(msg)? {
+        return Ok(Some(TokenAddress::Evm {
+            address,
+            chain: extract_chain_hint(msg)?,
+        }));
+    }
+
+    Ok(extract_sol_token_address(msg)?.map(TokenAddress::Solana))
+}
+
+pub fn extract_sol_token_address(msg: &str) -> Result<Option<String>, AppError> {
     let re = Regex::new("[1-9A-HJ-NP-Za-km-z]{32,44}")?;
 
     if let Some(caps) = re.captures(msg) {
@@ -11,11 +45,34 @@ pub fn extract_sol_token_address(msg: &str) -> Result<Option<String>, Error> {
     Ok(None)
 }
 
-pub fn extract_token_symbol(msg: &str) -> Result<Option<String>, Error> {
-    let re = Regex::new("\\$[a-zA-Z]+")?;
+/// Lowercased, so checksummed and plain spellings share scans and DB rows.
+pub fn extract_evm_token_address(msg: &str) -> Result<Option<String>, AppError> {
+    let re = Regex::new("\\b0x[0-9a-fA-F]{40}\\b")?;
 
     if let Some(caps) = re.captures(msg) {
-        return Ok(Some(caps[0].to_string()));
+        return Ok(Some(caps[0].to_lowercase()));
+    }
+
+    Ok(None)
+}
+
+/// An EVM chain named in the message, e.g. "base 0x…".
+pub fn extract_chain_hint(msg: &str) -> Result<Option<Chain>, AppError> {
+    let re = Regex::new("(?i)\\b(ethereum|eth|base|bsc|bnb|arbitrum|arb)\\b")?;
+
+    if let Some(caps) = re.captures(msg) {
+        return Ok(Some(caps[1].parse::<Chain>()?));
+    }
+
+    Ok(None)
+}
+
+/// The ticker of a `$SYMBOL` mention, without the `$`.
+pub fn extract_token_symbol(msg: &str) -> Result<Option<String>, AppError> {
+    let re = Regex::new("\\$([a-zA-Z][a-zA-Z0-9]*)\\b")?;
+
+    if let Some(caps) = re.captures(msg) {
+        return Ok(Some(caps[1].to_string()));
     }
 
     Ok(None)
@@ -1,2 +1,4 @@
+pub mod decimal;
 pub mod message_parser;
+pub mod single_flight;
 pub mod token_message_builder;
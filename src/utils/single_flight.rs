@@ -0,0 +1,197 @@
+use crate::errors::AppError;
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast;
+
+/// Coalesces concurrent calls for the same key: the first caller runs the
+/// future, later callers wait for its result instead of running their own.
+pub struct SingleFlight<T> {
+    in_flight: Mutex<HashMap<String, broadcast::Sender<T>>>,
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run<F>(&self, key: &str, fut: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let receiver = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(key) {
+                Some(sender) => Some(sender.subscribe()),
+                None => {
+                    let (sender, _) = broadcast::channel(1);
+                    in_flight.insert(String::from(key), sender);
+                    None
+                }
+            }
+        };
+
+        if let Some(mut receiver) = receiver {
+            // The leader was cancelled before finishing, so run it ourselves.
+            return match receiver.recv().await {
+                Ok(value) => value,
+                Err(_) => fut.await,
+            };
+        }
+
+        let mut guard = LeaderGuard {
+            in_flight: &self.in_flight,
+            key,
+            finished: false,
+        };
+        let value = fut.await;
+
+        if let Some(sender) = guard.finish() {
+            let _ = sender.send(value.clone());
+        }
+
+        value
+    }
+}
+
+/// Clears the in-flight entry if the leader is dropped mid-flight, which
+/// closes the channel and wakes the waiters.
+struct LeaderGuard<'a, T> {
+    in_flight: &'a Mutex<HashMap<String, broadcast::Sender<T>>>,
+    key: &'a str,
+    finished: bool,
+}
+
+impl<T> LeaderGuard<'_, T> {
+    fn finish(&mut self) -> Option<broadcast::Sender<T>> {
+        self.finished = true;
+        self.in_flight.lock().unwrap().remove(self.key)
+    }
+}
+
+impl<T> Drop for LeaderGuard<'_, T> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.in_flight.lock().unwrap().remove(self.key);
+        }
+    }
+}
+
+/// An error shared between every caller of a coalesced request.
+#[derive(Debug, Clone)]
+pub struct SharedError(Arc<AppError>);
+
+impl SharedError {
+    pub fn inner(&self) -> &AppError {
+        &self.0
+    }
+}
+
+impl From<AppError> for SharedError {
+    fn from(error: AppError) -> Self {
+        Self(Arc::new(error))
+    }
+}
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for SharedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::{sync::Notify, task::yield_now};
+
+    #[tokio::test]
+    async fn concurrent_callers_share_one_run() {
+        let single_flight: Arc<SingleFlight<u32>> = Arc::new(SingleFlight::new());
+        let runs = Arc::new(AtomicU32::new(0));
+        let release = Arc::new(Notify::new());
+
+        let leader = {
+            let (single_flight, runs, release) = (
+                Arc::clone(&single_flight),
+                Arc::clone(&runs),
+                Arc::clone(&release),
+            );
+            tokio::spawn(async move {
+                single_flight
+                    .run("mint", async {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        release.notified().await;
+                        1
+                    })
+                    .await
+            })
+        };
+        yield_now().await;
+
+        let waiter = {
+            let (single_flight, runs) = (Arc::clone(&single_flight), Arc::clone(&runs));
+            tokio::spawn(async move {
+                single_flight
+                    .run("mint", async {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        2
+                    })
+                    .await
+            })
+        };
+        yield_now().await;
+
+        release.notify_one();
+
+        assert_eq!(leader.await.unwrap(), 1);
+        assert_eq!(waiter.await.unwrap(), 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_leader_wakes_waiters() {
+        let single_flight: Arc<SingleFlight<u32>> = Arc::new(SingleFlight::new());
+
+        let leader = {
+            let single_flight = Arc::clone(&single_flight);
+            tokio::spawn(async move {
+                single_flight
+                    .run("mint", std::future::pending::<u32>())
+                    .await
+            })
+        };
+        yield_now().await;
+
+        let waiter = {
+            let single_flight = Arc::clone(&single_flight);
+            tokio::spawn(async move { single_flight.run("mint", async { 2 }).await })
+        };
+        yield_now().await;
+        assert!(!waiter.is_finished());
+
+        leader.abort();
+
+        assert_eq!(waiter.await.unwrap(), 2);
+        assert!(single_flight.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn finished_key_runs_again() {
+        let single_flight: SingleFlight<u32> = SingleFlight::new();
+
+        assert_eq!(single_flight.run("mint", async { 1 }).await, 1);
+        assert_eq!(single_flight.run("mint", async { 2 }).await, 2);
+    }
+}
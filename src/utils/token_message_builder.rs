@@ -1,12 +1,36 @@
-use crate::api::models::{SolTokenInfo, TokenLinks, TokenStats1H};
+use crate::api::{
+    models::{
+        BondingCurveInfo, Chain, DevProfile, LiquidityLock, MetadataAuthority, SniperReport,
+        SolTokenInfo, SymbolMatch, TokenAuthorities, TokenLinks, TokenPairStatsInfo,
+    },
+    solana_rpc::accounts::{Token2022Extensions, TokenProgram},
+};
 use crate::db::models::ScanType;
+use crate::errors::AppError;
+use crate::utils::decimal;
 use anyhow::Error;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serenity::{
-    all::{Color, CreateEmbed, CreateEmbedFooter, CreateMessage, User, UserId},
+    all::{
+        Color, CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption, EditMessage, User, UserId,
+    },
     http,
 };
 use std::sync::Arc;
+use tracing::Level;
+
+/// Shown in place of a section that could not be fetched.
+const NOT_AVAILABLE: &str = "n/a";
+
+/// Custom id of the `$SYMBOL` select menu.
+const SYMBOL_PICKER_ID: &str = "symbol_picker";
+/// Discord caps select menus at 25 options.
+const MAX_MENU_OPTIONS: usize = 25;
+/// Cap on the name and the symbol in an option label, which Discord limits
+/// to 100 characters.
+const MAX_MENU_NAME_LENGTH: usize = 40;
 
 pub struct TokenMessageBuilder {
     pub http: Arc<http::Http>,
@@ -18,7 +42,7 @@ impl TokenMessageBuilder {
         Self { http, token_info }
     }
 
-    pub async fn build(&self, author: &User, scan_type: ScanType) -> Result<CreateMessage, Error> {
+    pub async fn build(&self, author: &User, scan_type: &ScanType) -> Result<EditMessage, Error> {
         let content = self.build_content();
         let description = self.build_description()?;
         let footer = self.build_footer(author, scan_type).await?;
@@ -28,7 +52,87 @@ impl TokenMessageBuilder {
             .description(description)
             .footer(footer);
 
-        Ok(CreateMessage::new().content(content).add_embed(embed))
+        Ok(EditMessage::new().content(content).embed(embed))
+    }
+
+    /// Short embed for a scan that could not produce a token embed.
+    pub fn build_error(app_error: &AppError) -> EditMessage {
+        let title = match app_error.severity() {
+            Level::INFO => "❓ Token not found",
+            Level::WARN => "⏳ Temporarily unavailable",
+            _ => "⚠️ Scan failed",
+        };
+
+        let embed = CreateEmbed::new()
+            .color(Color::RED)
+            .title(title)
+            .description(app_error.user_message());
+
+        EditMessage::new().content("").embed(embed)
+    }
+
+    /// Select menu offering every token trading under `symbol`.
+    pub fn build_symbol_picker(symbol: &str, matches: &[SymbolMatch]) -> EditMessage {
+        let options: Vec<CreateSelectMenuOption> = matches
+            .iter()
+            .take(MAX_MENU_OPTIONS)
+            .map(|symbol_match| {
+                let name: String = symbol_match
+                    .name
+                    .chars()
+                    .take(MAX_MENU_NAME_LENGTH)
+                    .collect();
+                let symbol: String = symbol_match
+                    .symbol
+                    .chars()
+                    .take(MAX_MENU_NAME_LENGTH)
+                    .collect();
+                let mint_prefix: String = symbol_match.mint.chars().take(6).collect();
+                let mut description = format!(
+                    "FDV {} ⋅ Liq {}",
+                    Self::or_not_available(symbol_match.fully_diluted_value, |fdv| format!(
+                        "${}",
+                        Self::to_short_scale(fdv)
+                    )),
+                    Self::or_not_available(symbol_match.liquidity_usd, |liquidity| format!(
+                        "${}",
+                        Self::to_short_scale(liquidity)
+                    ))
+                );
+                if symbol_match.scanned_in_guild {
+                    description.push_str(" ⋅ ⭐ scanned here");
+                }
+
+                CreateSelectMenuOption::new(
+                    format!("{} (${}) ⋅ {}…", name, symbol, mint_prefix),
+                    &symbol_match.mint,
+                )
+                .description(description)
+            })
+            .collect();
+
+        let menu =
+            CreateSelectMenu::new(SYMBOL_PICKER_ID, CreateSelectMenuKind::String { options })
+                .placeholder("Pick the token to scan");
+
+        let content = if matches.len() > MAX_MENU_OPTIONS {
+            format!(
+                "🔎 {} tokens trade as `${}`, showing the top {}. Which one?",
+                matches.len(),
+                symbol,
+                MAX_MENU_OPTIONS
+            )
+        } else {
+            format!(
+                "🔎 {} tokens trade as `${}`, which one?",
+                matches.len(),
+                symbol
+            )
+        };
+
+        EditMessage::new()
+            .content(content)
+            .components(vec![CreateActionRow::SelectMenu(menu)])
     }
 
     fn build_content(&self) -> String {
@@ -42,11 +146,16 @@ impl TokenMessageBuilder {
             None
         };
 
+        let price_change_24h = match &self.token_info.pair_stats {
+            Some(pair_stats) => format!("{:.1}%", pair_stats.stats_24h.price_percent_change),
+            None => String::from(NOT_AVAILABLE),
+        };
+
         let mut content = format!(
-            "**{} [{}/{:.1}%] - ${}**",
+            "**{} [{}/{}] - ${}**",
             self.token_info.name,
-            Self::to_short_scale(self.token_info.fully_diluted_value),
-            self.token_info.stats_24h.price_percent_change,
+            Self::or_not_available(self.token_info.fully_diluted_value, Self::to_short_scale),
+            price_change_24h,
             self.token_info.symbol
         );
 
@@ -63,35 +172,130 @@ impl TokenMessageBuilder {
         let mint = &self.token_info.mint;
         let token_links = &self.token_info.links;
         let dev_address = self.token_info.dev.as_ref();
+        let update_authority = self
+            .token_info
+            .metadata_authority
+            .as_ref()
+            .map(|metadata_authority| &metadata_authority.update_authority);
         let holder_count = self.token_info.holder_count;
         let fdv = self.token_info.fully_diluted_value;
-        let usd_price = self.token_info.usd_price;
-        let liquidity_usd = self.token_info.liquidity_usd;
-        let token_stats_1h = &self.token_info.stats_1h;
-        let exchange_name = &self.token_info.token_pair_exchange_name;
-
-        embed_fields.push(format!("🌐 Solana @ {}", exchange_name));
-        embed_fields.push(format!("💰 USD: `${}`", Self::format_price(usd_price)));
-        embed_fields.push(format!("💎 FDV: `${}`", Self::to_short_scale(fdv)));
-        embed_fields.push(format!(
-            "💦 Liq: `${}`",
-            Self::to_short_scale(liquidity_usd)
-        ));
-        // embed_fields.push(format!("🕰️ Age: `{}`", token_age));
-        embed_fields.push(Self::format_hourly_stats(token_stats_1h));
+        let pair_stats = self.token_info.pair_stats.as_ref();
+
+        if let Some(bonding_curve) = &self.token_info.bonding_curve {
+            embed_fields.push(String::from("🌐 Solana @ Pump.fun bonding curve"));
+            embed_fields.push(format!(
+                "💰 USD: `${}`",
+                Self::format_price(bonding_curve.usd_price)
+            ));
+            embed_fields.push(format!(
+                "💎 MC: `${}`",
+                Self::to_short_scale(bonding_curve.market_cap_usd)
+            ));
+            if let Some(age) = self.format_age() {
+                embed_fields.push(age);
+            }
+            embed_fields.push(Self::format_bonding_curve(bonding_curve));
+        } else {
+            embed_fields.push(format!(
+                "🌐 {} @ {}",
+                self.token_info.chain.name(),
+                pair_stats.map_or(NOT_AVAILABLE, |pair_stats| &pair_stats.exchange_name)
+            ));
+            embed_fields.push(format!(
+                "💰 USD: `{}`",
+                Self::or_not_available(pair_stats, |pair_stats| format!(
+                    "${}",
+                    Self::format_price(pair_stats.usd_price)
+                ))
+            ));
+            embed_fields.push(format!(
+                "💎 FDV: `{}`",
+                Self::or_not_available(fdv, |fdv| format!("${}", Self::to_short_scale(fdv)))
+            ));
+            embed_fields.push(format!(
+                "💦 Liq: `{}`",
+                Self::or_not_available(pair_stats, |pair_stats| format!(
+                    "${}",
+                    Self::to_short_scale(pair_stats.liquidity_usd)
+                ))
+            ));
+            if let Some(liquidity_lock) = &self.token_info.liquidity_lock {
+                embed_fields.push(Self::format_liquidity_lock(liquidity_lock));
+            }
+            if let Some(age) = self.format_age() {
+                embed_fields.push(age);
+            }
+            embed_fields.push(Self::format_hourly_stats(pair_stats));
+        }
         embed_fields.push(String::from(""));
-        embed_fields.push(format!(
+        let mut holders_line = format!(
             "🤝 Total: `{}`",
-            Self::to_short_scale(holder_count as f64)
-        ));
+            Self::or_not_available(holder_count, |holder_count| Self::to_short_scale(
+                Decimal::from(holder_count)
+            ))
+        );
+        if let Some(concentration) = &self.token_info.holder_concentration {
+            holders_line.push_str(&format!(
+                " ⋅ Top 1/10/{} excl. pools: `{:.1}%` `{:.1}%` `{:.1}%`",
+                concentration.top_n,
+                concentration.top_1_percent,
+                concentration.top_10_percent,
+                concentration.top_n_percent
+            ));
+        }
+        embed_fields.push(holders_line);
+
+        if let Some(snipers) = &self.token_info.snipers {
+            embed_fields.push(Self::format_snipers(snipers));
+        }
+
+        if let Some(dev_profile) = &self.token_info.dev_profile {
+            embed_fields.push(Self::format_dev_profile(dev_profile));
+        }
+
+        if let Some(authorities) = &self.token_info.authorities {
+            embed_fields.push(Self::format_authorities(
+                authorities,
+                self.token_info.metadata_authority.as_ref(),
+            ));
+        }
+
+        if let (Some(TokenProgram::Token2022), Some(extensions)) = (
+            self.token_info.token_program,
+            &self.token_info.token_extensions,
+        ) {
+            embed_fields.push(Self::format_token_2022_extensions(extensions));
+        }
 
-        if let Some(socials) = Self::format_socials(token_links, dev_address) {
-            embed_fields.push(format!("💼 Socials: {}", socials));
+        match Self::format_socials(
+            token_links.as_ref().unwrap_or(&TokenLinks::default()),
+            dev_address,
+            update_authority,
+        ) {
+            Some(socials) => embed_fields.push(format!("💼 Socials: {}", socials)),
+            None if token_links.is_none() => {
+                embed_fields.push(format!("💼 Socials: {}", NOT_AVAILABLE))
+            }
+            None => {}
+        }
+
+        if self.token_info.details_pending {
+            embed_fields.push(String::from(
+                "⏳ Loading snipers, holder split, liquidity lock and dev history…",
+            ));
         }
 
+        if !self.token_info.unavailable_sections.is_empty() {
+            embed_fields.push(format!(
+                "⚠️ Partial data: {} unavailable",
+                self.token_info.unavailable_sections.join(", ")
+            ));
+        }
+
+        let (dexscreener_chain, defined_chain) = Self::chart_chain_ids(self.token_info.chain);
         embed_fields.push(format!(
-            "💹 Chart: [DEX](https://dexscreener.com/solana/{}) ⋅ [DEF](https://www.defined.fi/sol/{})",
-            mint, mint
+            "💹 Chart: [DEX](https://dexscreener.com/{}/{}) ⋅ [DEF](https://www.defined.fi/{}/{})",
+            dexscreener_chain, mint, defined_chain, mint
         ));
 
         let mut description = embed_fields.join("\n");
@@ -103,21 +307,39 @@ impl TokenMessageBuilder {
     async fn build_footer(
         &self,
         author: &User,
-        scan_type: ScanType,
+        scan_type: &ScanType,
     ) -> Result<CreateEmbedFooter, Error> {
         let mut footer = match scan_type {
             ScanType::FirstScan(token_scan) => CreateEmbedFooter::new(format!(
                 "{} 💨 You are first! @ {}",
                 author.display_name(),
-                Self::to_short_scale(token_scan.fdv)
-            )),
-            ScanType::Scanned(token_scan) => CreateEmbedFooter::new(format!(
-                "{} 🏆 {} @ {} ⋅ {}",
-                author.display_name(),
-                self.http.get_user(UserId::from(token_scan.user_id)).await?.display_name(),
-                Self::to_short_scale(token_scan.fdv),
-                Self::format_duration(&token_scan.scanned_at)
+                Self::or_not_available(token_scan.fdv, Self::to_short_scale)
             )),
+            ScanType::Scanned(token_scan) => {
+                let mut text = format!(
+                    "{} 🏆 {} @ {}",
+                    author.display_name(),
+                    self.http
+                        .get_user(UserId::from(token_scan.user_id))
+                        .await?
+                        .display_name(),
+                    Self::or_not_available(token_scan.fdv, Self::to_short_scale),
+                );
+                if let Some(multiplier) = self
+                    .token_info
+                    .fully_diluted_value
+                    .zip(token_scan.fdv)
+                    .and_then(|(fdv, call_fdv)| Self::format_multiplier(fdv, call_fdv))
+                {
+                    text.push_str(&format!(" ⋅ {}", multiplier));
+                }
+                text.push_str(&format!(
+                    " ⋅ {}",
+                    Self::format_duration(&token_scan.scanned_at)
+                ));
+
+                CreateEmbedFooter::new(text)
+            }
         };
 
         if let Some(avatar_url) = author.avatar_url() {
@@ -127,11 +349,26 @@ impl TokenMessageBuilder {
         Ok(footer)
     }
 
-    fn format_hourly_stats(pair_stats_1h: &TokenStats1H) -> String {
+    fn format_age(&self) -> Option<String> {
+        let launched_at = self.token_info.launched_at.as_ref()?;
+
+        let mut line = format!("🕰️ Age: `{}`", Self::format_duration(launched_at));
+        if self.token_info.is_fresh {
+            line.push_str(" ⋅ 🆕 FRESH");
+        }
+
+        Some(line)
+    }
+
+    fn format_hourly_stats(pair_stats: Option<&TokenPairStatsInfo>) -> String {
+        let Some(pair_stats) = pair_stats else {
+            return format!("📈 1H: `{}`", NOT_AVAILABLE);
+        };
+
+        let pair_stats_1h = &pair_stats.stats_1h;
         let price_change = pair_stats_1h.price_percent_change;
-        let buy_volume = pair_stats_1h.buy_volume;
-        let sell_volume = pair_stats_1h.sell_volume;
-        let volume = Self::to_short_scale(buy_volume + sell_volume);
+        let volume =
+            Self::to_short_scale(decimal::from_f64(pair_stats_1h.volume).unwrap_or_default());
         let buys = pair_stats_1h.buys;
         let sells = pair_stats_1h.sells;
 
@@ -141,28 +378,183 @@ impl TokenMessageBuilder {
         )
     }
 
-    fn format_price(price: f64) -> String {
+    fn format_authorities(
+        authorities: &TokenAuthorities,
+        metadata_authority: Option<&MetadataAuthority>,
+    ) -> String {
+        let status = |authority: &Option<String>| match authority {
+            Some(_) => "⚠️",
+            None => "✅",
+        };
+
+        let mut line = format!(
+            "🔐 Mint: {} ⋅ Freeze: {}",
+            status(&authorities.mint_authority),
+            status(&authorities.freeze_authority)
+        );
+
+        if let Some(metadata_authority) = metadata_authority {
+            line.push_str(if metadata_authority.is_mutable {
+                " ⋅ Meta: ⚠️ mutable"
+            } else {
+                " ⋅ Meta: ✅"
+            });
+        }
+
+        line
+    }
+
+    fn format_token_2022_extensions(extensions: &Token2022Extensions) -> String {
+        let mut warnings: Vec<String> = vec![];
+
+        if let Some(basis_points) = extensions.transfer_fee_basis_points.filter(|bps| *bps > 0) {
+            warnings.push(format!(
+                "⚠️ Transfer fee `{:.2}%`",
+                basis_points as f64 / 100.0
+            ));
+        }
+
+        if extensions.permanent_delegate.is_some() {
+            warnings.push(String::from("⚠️ Permanent delegate"));
+        }
+
+        if extensions.transfer_hook_program.is_some() {
+            warnings.push(String::from("⚠️ Transfer hook"));
+        }
+
+        if extensions.non_transferable {
+            warnings.push(String::from("⛔ Non-transferable"));
+        }
+
+        if extensions.default_account_state_frozen {
+            warnings.push(String::from("⛔ Frozen by default"));
+        }
+
+        if warnings.is_empty() {
+            warnings.push(String::from("✅ No risky extensions"));
+        }
+
+        format!("🧩 Token-2022: {}", warnings.join(" ⋅ "))
+    }
+
+    fn format_bonding_curve(bonding_curve: &BondingCurveInfo) -> String {
+        const SEGMENTS: usize = 10;
+
+        let filled = ((bonding_curve.progress_percent / 100.0) * SEGMENTS as f64).round() as usize;
+        let progress_bar = format!(
+            "{}{}",
+            "▰".repeat(filled.min(SEGMENTS)),
+            "▱".repeat(SEGMENTS - filled.min(SEGMENTS))
+        );
+
+        format!(
+            "🎓 Curve: `{}` `{:.1}%` ⋅ `{:.1} SOL`",
+            progress_bar, bonding_curve.progress_percent, bonding_curve.sol_reserves
+        )
+    }
+
+    fn format_liquidity_lock(liquidity_lock: &LiquidityLock) -> String {
+        let burned = liquidity_lock.burned_percent;
+        let locked = liquidity_lock.locked_percent;
+
+        if burned + locked < 1.0 {
+            return String::from("💧 LP ⚠️ unlocked");
+        }
+
+        let mut line = format!("💧 LP 🔥 `{:.0}%`", burned);
+        if locked >= 1.0 {
+            line.push_str(&format!(" ⋅ 🔒 `{:.0}%`", locked));
+        }
+
+        line
+    }
+
+    fn format_snipers(snipers: &SniperReport) -> String {
+        const BUNDLE_WARNING_HELD_PERCENT: f64 = 10.0;
+
+        let mut line = format!(
+            "🎯 Snipers: `{}` in {} slots ⋅ Hold: `{:.1}%`",
+            snipers.wallet_count, snipers.slot_window, snipers.held_percent
+        );
+
+        if snipers.held_percent >= BUNDLE_WARNING_HELD_PERCENT {
+            line.push_str(" ⚠️ bundled");
+        }
+
+        line
+    }
+
+    fn format_dev_profile(dev_profile: &DevProfile) -> String {
+        let mut line = format!(
+            "👨‍💻 Dev: `{}` launches (`{}` seen), `{}` above $1M",
+            dev_profile.launch_count, dev_profile.tracked_count, dev_profile.above_1m_count
+        );
+
+        if let (Some(peak_fdv), Some(last_fdv)) =
+            (dev_profile.best_peak_fdv, dev_profile.best_last_fdv)
+        {
+            line.push_str(&format!(
+                " ⋅ Best: `${}` → `${}`",
+                Self::to_short_scale(peak_fdv),
+                Self::to_short_scale(last_fdv)
+            ));
+        }
+
+        if dev_profile.rugged_count > 0 {
+            line.push_str(&format!(" ⋅ ☠️ `{}` rugged", dev_profile.rugged_count));
+        }
+
+        line
+    }
+
+    /// Chain slugs used in DexScreener and Defined URLs.
+    fn chart_chain_ids(chain: Chain) -> (&'static str, &'static str) {
+        match chain {
+            Chain::Solana => ("solana", "sol"),
+            Chain::Ethereum => ("ethereum", "eth"),
+            Chain::Base => ("base", "base"),
+            Chain::Bsc => ("bsc", "bsc"),
+            Chain::Arbitrum => ("arbitrum", "arb"),
+        }
+    }
+
+    fn or_not_available<T>(value: Option<T>, format: impl FnOnce(T) -> String) -> String {
+        value.map_or_else(|| String::from(NOT_AVAILABLE), format)
+    }
+
+    fn format_price(price: Decimal) -> String {
         match price {
-            p if p >= 1000.0 => format!("{:.0}", p),
-            p if p >= 1.0 => format!("{:.2}", p),
-            p if p >= 0.1 => format!("{:.4}", p),
-            p if p >= 0.01 => format!("{:.5}", p),
-            p if p >= 0.001 => format!("{:.6}", p),
-            p if p >= 0.0001 => format!("{:.7}", p),
-            p if p >= 0.00001 => format!("{:.8}", p),
+            p if p >= Decimal::ONE_THOUSAND => format!("{:.0}", p),
+            p if p >= Decimal::ONE => format!("{:.2}", p),
+            p if p >= Decimal::new(1, 1) => format!("{:.4}", p),
+            p if p >= Decimal::new(1, 2) => format!("{:.5}", p),
+            p if p >= Decimal::new(1, 3) => format!("{:.6}", p),
+            p if p >= Decimal::new(1, 4) => format!("{:.7}", p),
+            p if p >= Decimal::new(1, 5) => format!("{:.8}", p),
             _ => format!("{:.9}", price),
         }
     }
 
-    fn to_short_scale(num: f64) -> String {
+    fn to_short_scale(num: Decimal) -> String {
+        let billion = Decimal::from(1_000_000_000);
+        let million = Decimal::from(1_000_000);
+
         match num {
-            n if n > 1000000000.0 => format!("{:.1}B", n / 1000000000.0),
-            n if n > 1000000.0 => format!("{:.1}M", n / 1000000.0),
-            n if n > 1000.0 => format!("{:.1}K", n / 1000.0),
+            n if n > billion => format!("{:.1}B", n / billion),
+            n if n > million => format!("{:.1}M", n / million),
+            n if n > Decimal::ONE_THOUSAND => format!("{:.1}K", n / Decimal::ONE_THOUSAND),
             _ => format!("{:.0}", num),
         }
     }
 
+    /// How many times the FDV has grown since the first call, `None` if the
+    /// call was made without a known FDV.
+    fn format_multiplier(fdv: Decimal, call_fdv: Decimal) -> Option<String> {
+        fdv.checked_div(call_fdv)
+            .filter(|_| call_fdv > Decimal::ZERO)
+            .map(|multiplier| format!("{:.1}x", multiplier))
+    }
+
     fn format_duration(scanned_at: &DateTime<Utc>) -> String {
         let duration = Utc::now().signed_duration_since(scanned_at);
         match duration {
@@ -176,7 +568,11 @@ impl TokenMessageBuilder {
         }
     }
 
-    fn format_socials(token_links: &TokenLinks, dev_address: Option<&String>) -> Option<String> {
+    fn format_socials(
+        token_links: &TokenLinks,
+        dev_address: Option<&String>,
+        update_authority: Option<&String>,
+    ) -> Option<String> {
         let mut links: Vec<String> = vec![];
 
         if let Some(twitter) = &token_links.twitter {
@@ -203,6 +599,13 @@ impl TokenMessageBuilder {
             links.push(format!("[Dev](https://solscan.io/account/{})", dev_wallet));
         }
 
+        if let Some(update_authority) = update_authority {
+            links.push(format!(
+                "[Upd](https://solscan.io/account/{})",
+                update_authority
+            ));
+        }
+
         if links.is_empty() {
             return None;
         }